@@ -0,0 +1,146 @@
+use std::borrow::Cow;
+
+use crate::Workload;
+
+/// A property whose value differs between two workloads, as compared by
+/// [`Workload::diff`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FieldDiff {
+    /// The YCSB property name of the field
+    pub field: Cow<'static, str>,
+    /// The value in the workload `diff` was called on (`None` if unset)
+    pub left: Option<String>,
+    /// The value in the workload passed to `diff` (`None` if unset)
+    pub right: Option<String>,
+}
+
+impl Workload {
+    /// Every property whose value differs between `self` and `other`, in
+    /// the order they appear in a properties file
+    pub fn diff(&self, other: &Workload) -> Vec<FieldDiff> {
+        let left = self.properties();
+        let right = other.properties();
+        let lookup = |properties: &[(Cow<'static, str>, String)], field: &str| {
+            properties
+                .iter()
+                .find(|(key, _)| key == field)
+                .map(|(_, value)| value.clone())
+        };
+        let mut fields: Vec<_> = left.iter().map(|(key, _)| key.clone()).collect();
+        for (key, _) in &right {
+            if !fields.contains(key) {
+                fields.push(key.clone());
+            }
+        }
+        fields
+            .into_iter()
+            .filter_map(|field| {
+                let left = lookup(&left, &field);
+                let right = lookup(&right, &field);
+                (left != right).then_some(FieldDiff { field, left, right })
+            })
+            .collect()
+    }
+
+    /// The property name and value of every property that differs from
+    /// [`Workload::default`], e.g. to write a minimal override file
+    pub fn non_default_fields(&self) -> Vec<(String, String)> {
+        Workload::default()
+            .diff(self)
+            .into_iter()
+            .filter_map(|diff| Some((diff.field.into_owned(), diff.right?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Distribution, FieldSpec, WorkloadBuilder};
+
+    #[test]
+    fn diff() {
+        let a = Workload::a(1000, 1000);
+        assert!(a.diff(&a).is_empty());
+        let b = WorkloadBuilder::default()
+            .record_count(1000)
+            .operation_count(2000)
+            .seed(1)
+            .build()
+            .unwrap();
+        let diff = Workload::default().diff(&b);
+        assert_eq!(
+            diff,
+            vec![
+                FieldDiff {
+                    field: "recordcount".into(),
+                    left: Some("1000000".to_owned()),
+                    right: Some("1000".to_owned()),
+                },
+                FieldDiff {
+                    field: "operationcount".into(),
+                    left: Some("3000000".to_owned()),
+                    right: Some("2000".to_owned()),
+                },
+                FieldDiff {
+                    field: "seed".into(),
+                    left: None,
+                    right: Some("1".to_owned()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn non_default_fields() {
+        assert!(Workload::default().non_default_fields().is_empty());
+        let workload = WorkloadBuilder::default()
+            .record_count(1000)
+            .table("orders".to_owned())
+            .build()
+            .unwrap();
+        let fields = workload.non_default_fields();
+        assert_eq!(
+            fields,
+            [
+                ("recordcount".to_owned(), "1000".to_owned()),
+                ("table".to_owned(), "orders".to_owned()),
+            ]
+        );
+        let mut overrides = Workload::default();
+        overrides.apply_overrides(&fields).unwrap();
+        assert_eq!(overrides, workload);
+    }
+
+    #[test]
+    fn field_specs() {
+        let count = WorkloadBuilder::default().field_count(2).build().unwrap();
+        let specs = WorkloadBuilder::default()
+            .field_count(2)
+            .field_specs(vec![
+                FieldSpec::new(10, Distribution::Constant),
+                FieldSpec::new(20, Distribution::Uniform),
+            ])
+            .build()
+            .unwrap();
+        let fields: Vec<_> = count
+            .diff(&specs)
+            .into_iter()
+            .map(|diff| diff.field)
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                "field.0.length",
+                "field.0.lengthdistribution",
+                "field.1.length",
+                "field.1.lengthdistribution"
+            ]
+        );
+        let mut overrides = Workload::default();
+        overrides
+            .apply_overrides(&specs.non_default_fields())
+            .unwrap();
+        assert_eq!(overrides, specs);
+    }
+}
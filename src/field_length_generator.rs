@@ -0,0 +1,98 @@
+use rand::rngs::SmallRng;
+
+use crate::{
+    generator::{ConstantGenerator, Generator, UniformLongGenerator, ZipfianGenerator},
+    Distribution, RngSalt, Workload,
+};
+
+/// Chooses the length of each field of a record according to the field
+/// length distribution
+pub struct FieldLengthGenerator {
+    rng: SmallRng,
+    generator: Box<dyn Generator<u64> + Send>,
+}
+
+impl FieldLengthGenerator {
+    /// # Panics
+    /// If `distribution` is not constant, uniform or zipfian
+    pub fn new(field_length: u64, distribution: Distribution, rng: SmallRng) -> Self {
+        let generator: Box<dyn Generator<u64> + Send> = match distribution {
+            Distribution::Constant => Box::new(ConstantGenerator::new(field_length)),
+            Distribution::Uniform => Box::new(UniformLongGenerator::new(1, field_length)),
+            Distribution::Zipfian => Box::new(ZipfianGenerator::from_range(1, field_length)),
+            distribution => panic!("unsupported field length distribution {}", distribution),
+        };
+        Self { rng, generator }
+    }
+
+    /// Choose field lengths for the records of `workload`, seeded by
+    /// [`Workload::rng`]
+    pub fn from_workload(workload: &Workload) -> Self {
+        Self::new(
+            workload.field_length,
+            workload.field_length_distribution,
+            workload.rng(),
+        )
+    }
+
+    /// Choose lengths for field `index` of the records of `workload`,
+    /// seeded by the workload's seed mixed with `index` so that each field
+    /// draws its own sequence of lengths
+    pub fn for_field(workload: &Workload, index: u64) -> Self {
+        let spec = workload.field_spec(index);
+        Self::new(
+            spec.length(),
+            spec.length_distribution(),
+            workload.salted_rng(RngSalt::FieldLength(index)),
+        )
+    }
+
+    pub fn next_length(&mut self) -> u64 {
+        self.generator.next_value(&mut self.rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WorkloadBuilder;
+    use rand::SeedableRng;
+
+    #[test]
+    fn lengths_within_range() {
+        let mut constant =
+            FieldLengthGenerator::new(100, Distribution::Constant, SmallRng::seed_from_u64(0));
+        assert_eq!(constant.next_length(), 100);
+        for distribution in [Distribution::Uniform, Distribution::Zipfian] {
+            let mut generator =
+                FieldLengthGenerator::new(100, distribution, SmallRng::seed_from_u64(0));
+            for _ in 0..10000 {
+                assert!((1..=100).contains(&generator.next_length()));
+            }
+        }
+    }
+
+    #[test]
+    fn fields_draw_independently() {
+        let workload = WorkloadBuilder::default()
+            .field_count(2)
+            .field_length_distribution(Distribution::Uniform)
+            .seed(3)
+            .build()
+            .unwrap();
+        let lengths = |index| {
+            let mut generator = FieldLengthGenerator::for_field(&workload, index);
+            (0..20).map(|_| generator.next_length()).collect::<Vec<_>>()
+        };
+        assert_eq!(lengths(0), lengths(0));
+        assert_ne!(lengths(0), lengths(1));
+    }
+
+    #[test]
+    fn zipfian_favours_short_fields() {
+        let mut generator =
+            FieldLengthGenerator::new(100, Distribution::Zipfian, SmallRng::seed_from_u64(0));
+        let short = (0..10000).filter(|_| generator.next_length() <= 10).count();
+        assert!(short > 5000);
+    }
+}
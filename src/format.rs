@@ -0,0 +1,205 @@
+//! Telling the supported workload formats apart by their content
+
+use crate::{Workload, WorkloadError};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Format {
+    Toml,
+    Json,
+    Properties,
+}
+
+/// What a single line says about the format of the document
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Hint {
+    Toml,
+    Properties,
+    /// Valid and equivalent in both formats, e.g. `recordcount=1000`
+    Either,
+}
+
+fn line_hint(line: &str) -> Hint {
+    if line.starts_with('!') {
+        return Hint::Properties;
+    }
+    if line.starts_with('[') {
+        return Hint::Toml;
+    }
+    let value = match line.split_once('=') {
+        Some((_, value)) => value.trim(),
+        // `key: value` and `key value` are properties only
+        None => return Hint::Properties,
+    };
+    if value.starts_with(['"', '\'', '[', '{']) {
+        return Hint::Toml;
+    }
+    // ignore a trailing TOML comment, properties would keep it in the value
+    let value = value.split('#').next().unwrap_or_default().trim();
+    if value == "true" || value == "false" || value.parse::<f64>().is_ok() {
+        Hint::Either
+    } else {
+        Hint::Properties
+    }
+}
+
+/// The number of brackets still open after `line`, ignoring any trailing
+/// comment
+fn bracket_depth(open: usize, line: &str) -> usize {
+    let code = line.split('#').next().unwrap_or_default();
+    code.chars().fold(open, |depth, c| match c {
+        '[' => depth + 1,
+        ']' => depth.saturating_sub(1),
+        _ => depth,
+    })
+}
+
+/// Guess the format of `s`: JSON if it starts with `{`, TOML if any line
+/// is a table header or has a quoted or array value, and Java properties
+/// otherwise
+fn sniff(s: &str) -> Result<Format, WorkloadError> {
+    if s.trim_start().starts_with('{') {
+        return Ok(Format::Json);
+    }
+    let mut toml_line = None;
+    let mut properties_line = None;
+    // brackets left open by a TOML array spanning several lines, whose
+    // continuation lines such as `0.95,` say nothing about the format
+    let mut open_brackets = 0;
+    for (index, line) in s.lines().enumerate() {
+        let line = line.trim();
+        if open_brackets > 0 {
+            open_brackets = bracket_depth(open_brackets, line);
+            continue;
+        }
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let hint = line_hint(line);
+        if hint == Hint::Toml && !line.starts_with('[') {
+            if let Some((_, value)) = line.split_once('=') {
+                open_brackets = bracket_depth(0, value);
+            }
+        }
+        match hint {
+            Hint::Toml => {
+                toml_line.get_or_insert(index + 1);
+            }
+            Hint::Properties => {
+                properties_line.get_or_insert(index + 1);
+            }
+            Hint::Either => {}
+        }
+    }
+    match (toml_line, properties_line) {
+        (Some(toml_line), Some(properties_line)) => Err(WorkloadError::AmbiguousFormat {
+            toml_line,
+            properties_line,
+        }),
+        (Some(_), None) => Ok(Format::Toml),
+        (None, _) => Ok(Format::Properties),
+    }
+}
+
+impl Workload {
+    /// Parse a workload in TOML, JSON or YCSB's Java `.properties` format,
+    /// telling them apart by content.
+    ///
+    /// Fails with [`WorkloadError::AmbiguousFormat`] if some lines only
+    /// make sense as TOML and others only as properties.
+    pub fn from_str_auto(s: &str) -> Result<Self, WorkloadError> {
+        match sniff(s)? {
+            Format::Toml => Workload::from_toml_str(s),
+            Format::Json => Workload::from_json_str(s),
+            Format::Properties => Workload::from_properties_str(s),
+        }
+    }
+}
+
+impl TryFrom<&str> for Workload {
+    type Error = WorkloadError;
+
+    /// See [`Workload::from_str_auto`]
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Workload::from_str_auto(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StandardWorkload;
+
+    #[test]
+    fn sniff_formats() {
+        assert_eq!(sniff("{\"recordcount\": 1}").ok(), Some(Format::Json));
+        assert_eq!(sniff("[histogram]\nbuckets = 100").ok(), Some(Format::Toml));
+        assert_eq!(sniff("workload = \"core\"").ok(), Some(Format::Toml));
+        assert_eq!(
+            sniff("workload=core\nrecordcount=1").ok(),
+            Some(Format::Properties)
+        );
+        assert_eq!(sniff("status.interval: 10").ok(), Some(Format::Properties));
+        assert_eq!(
+            sniff("recordcount=1\nreadallfields=true").ok(),
+            Some(Format::Properties)
+        );
+        assert!(matches!(
+            sniff("# header\nworkload = \"core\"\ntable=usertable"),
+            Err(WorkloadError::AmbiguousFormat {
+                toml_line: 2,
+                properties_line: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn sniff_multi_line_array() {
+        let toml = "recordcount = 1000
+requestdistribution = \"zipfian\"
+
+[hdrhistogram]
+percentiles = [
+  90.0,
+  95.0, # tail
+  99.9,
+]
+outputfile = \"hdr.txt\"
+";
+        assert_eq!(sniff(toml).ok(), Some(Format::Toml));
+        assert_eq!(
+            Workload::from_str_auto(toml)
+                .unwrap()
+                .hdrhistogram()
+                .percentiles(),
+            [90.0, 95.0, 99.9]
+        );
+        // properties after the array still count
+        assert!(matches!(
+            sniff("percentiles = [\n  90.0,\n]\ntable=usertable"),
+            Err(WorkloadError::AmbiguousFormat {
+                toml_line: 1,
+                properties_line: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn from_str_auto() {
+        let workload = Workload::a(1000, 1000);
+        let json = workload.to_json_string().unwrap();
+        assert_eq!(Workload::from_str_auto(&json).unwrap(), workload);
+        for standard in StandardWorkload::ALL {
+            assert_eq!(
+                Workload::from_str_auto(standard.toml()).unwrap(),
+                Workload::standard(standard)
+            );
+        }
+        assert_eq!(
+            Workload::from_str_auto("workload=core\nrecordcount=10")
+                .unwrap()
+                .record_count(),
+            10
+        );
+        assert_eq!(Workload::try_from(json.as_str()).unwrap(), workload);
+    }
+}
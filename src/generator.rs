@@ -2,6 +2,10 @@ mod acknowledged_counter_generator;
 mod constant_generator;
 mod counter_generator;
 mod discrete_generator;
+mod exponential_generator;
+mod hotspot_integer_generator;
+mod scrambled_zipfian_generator;
+mod sequential_generator;
 mod uniform_long_generator;
 mod zipfian_generator;
 
@@ -9,9 +13,13 @@ pub use acknowledged_counter_generator::AcknowledgedCounterGenerator;
 pub use constant_generator::ConstantGenerator;
 pub use counter_generator::CounterGenerator;
 pub use discrete_generator::{DiscreteGenerator, WeightPair};
+pub use exponential_generator::ExponentialGenerator;
+pub use hotspot_integer_generator::HotspotIntegerGenerator;
 use rand::prelude::SmallRng;
+pub use scrambled_zipfian_generator::ScrambledZipfianGenerator;
+pub use sequential_generator::SequentialGenerator;
 pub use uniform_long_generator::UniformLongGenerator;
-pub use zipfian_generator::ZipfianGenerator;
+pub use zipfian_generator::{ZipfianGenerator, ZIPFIAN_CONSTANT};
 
 use std::string::ToString;
 
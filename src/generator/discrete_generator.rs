@@ -40,7 +40,8 @@ impl<T: ToString + Clone + Send> Generator<T> for DiscreteGenerator<T> {
             }
             val -= pw;
         }
-        unreachable!();
+        // rounding errors may leave a sliver of probability past the last value
+        self.values.last().unwrap().value.clone()
     }
 }
 
@@ -0,0 +1,31 @@
+use super::{Generator, NumberGenerator};
+use rand::prelude::*;
+
+pub struct ExponentialGenerator {
+    gamma: f64,
+}
+
+impl ExponentialGenerator {
+    /// Values below `range` are produced `percentile` percent of the time
+    pub fn new(percentile: f64, range: f64) -> Self {
+        Self {
+            gamma: -(1.0 - percentile / 100.0).ln() / range,
+        }
+    }
+
+    pub fn from_mean(mean: f64) -> Self {
+        Self { gamma: 1.0 / mean }
+    }
+}
+
+impl Generator<u64> for ExponentialGenerator {
+    fn next_value(&self, rng: &mut SmallRng) -> u64 {
+        (-rng.gen::<f64>().ln() / self.gamma) as u64
+    }
+}
+
+impl NumberGenerator<u64> for ExponentialGenerator {
+    fn mean(&self) -> u64 {
+        (1.0 / self.gamma) as u64
+    }
+}
@@ -0,0 +1,74 @@
+use super::{Generator, NumberGenerator};
+use rand::prelude::*;
+
+/// Chooses from a hot set at the start of a range with a given
+/// probability, and uniformly from the rest of the range otherwise
+pub struct HotspotIntegerGenerator {
+    lower_bound: u64,
+    hot_interval: u64,
+    cold_interval: u64,
+    hot_operation_fraction: f64,
+}
+
+impl HotspotIntegerGenerator {
+    /// Choose from `lower_bound..=upper_bound`, the first
+    /// `hot_data_fraction` of which is chosen `hot_operation_fraction` of
+    /// the time
+    pub fn new(
+        lower_bound: u64,
+        upper_bound: u64,
+        hot_data_fraction: f64,
+        hot_operation_fraction: f64,
+    ) -> Self {
+        let interval = upper_bound - lower_bound + 1;
+        let hot_interval = (interval as f64 * hot_data_fraction) as u64;
+        Self {
+            lower_bound,
+            hot_interval,
+            cold_interval: interval - hot_interval,
+            hot_operation_fraction,
+        }
+    }
+}
+
+impl Generator<u64> for HotspotIntegerGenerator {
+    fn next_value(&self, rng: &mut SmallRng) -> u64 {
+        let hot = self.cold_interval == 0
+            || (self.hot_interval > 0 && rng.gen::<f64>() < self.hot_operation_fraction);
+        if hot {
+            self.lower_bound + rng.gen_range(0..self.hot_interval)
+        } else {
+            self.lower_bound + self.hot_interval + rng.gen_range(0..self.cold_interval)
+        }
+    }
+}
+
+impl NumberGenerator<u64> for HotspotIntegerGenerator {
+    fn mean(&self) -> u64 {
+        let hot_mean = self.lower_bound as f64 + self.hot_interval as f64 / 2.0;
+        let cold_mean =
+            self.lower_bound as f64 + self.hot_interval as f64 + self.cold_interval as f64 / 2.0;
+        (self.hot_operation_fraction * hot_mean + (1.0 - self.hot_operation_fraction) * cold_mean)
+            as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hot_set() {
+        let generator = HotspotIntegerGenerator::new(0, 999, 0.2, 0.8);
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut hot = 0;
+        for _ in 0..100000 {
+            let value = generator.next_value(&mut rng);
+            assert!(value < 1000);
+            if value < 200 {
+                hot += 1;
+            }
+        }
+        assert!((hot as f64 / 100000. - 0.8).abs() < 0.01);
+    }
+}
@@ -0,0 +1,60 @@
+use super::{Generator, NumberGenerator, ZipfianGenerator, ZIPFIAN_CONSTANT};
+use crate::fnv_hash64;
+use rand::prelude::*;
+
+/// Number of items of the underlying zipfian distribution
+const ITEM_COUNT: u64 = 10_000_000_000;
+/// Precomputed zeta of `ITEM_COUNT` items with `ZIPFIAN_CONSTANT`
+const ZETAN: f64 = 26.46902820178302;
+
+/// A zipfian distribution whose popular items are scattered across the
+/// whole range instead of being clustered at the start of it
+pub struct ScrambledZipfianGenerator {
+    generator: ZipfianGenerator,
+    min: u64,
+    item_count: u64,
+}
+
+impl ScrambledZipfianGenerator {
+    pub fn from_items(items: u64) -> Self {
+        Self::from_range(0, items - 1)
+    }
+
+    pub fn from_range(min: u64, max: u64) -> Self {
+        Self {
+            generator: ZipfianGenerator::new(0, ITEM_COUNT - 1, ZIPFIAN_CONSTANT, ZETAN),
+            min,
+            item_count: max - min + 1,
+        }
+    }
+}
+
+impl Generator<u64> for ScrambledZipfianGenerator {
+    fn next_value(&self, rng: &mut SmallRng) -> u64 {
+        self.min + fnv_hash64(self.generator.next_value(rng)) % self.item_count
+    }
+}
+
+impl NumberGenerator<u64> for ScrambledZipfianGenerator {
+    fn mean(&self) -> u64 {
+        self.min + (self.item_count - 1) / 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_and_max_parameter() {
+        let min = 5;
+        let max = 10;
+        let generator = ScrambledZipfianGenerator::from_range(min, max);
+        let mut rng = SmallRng::from_entropy();
+        for _i in 0..100000 {
+            let val = generator.next_value(&mut rng);
+            assert!(val >= min);
+            assert!(val <= max);
+        }
+    }
+}
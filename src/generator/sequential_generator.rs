@@ -0,0 +1,45 @@
+use super::{Generator, NumberGenerator};
+use rand::prelude::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+pub struct SequentialGenerator {
+    counter: AtomicU64,
+    count_start: u64,
+    interval: u64,
+}
+
+impl SequentialGenerator {
+    /// Cycle through `count_start..=count_end` in order
+    pub fn new(count_start: u64, count_end: u64) -> Self {
+        Self {
+            counter: AtomicU64::new(0),
+            count_start,
+            interval: count_end - count_start + 1,
+        }
+    }
+}
+
+impl Generator<u64> for SequentialGenerator {
+    fn next_value(&self, _rng: &mut SmallRng) -> u64 {
+        self.count_start + self.counter.fetch_add(1, Ordering::SeqCst) % self.interval
+    }
+}
+
+impl NumberGenerator<u64> for SequentialGenerator {
+    fn mean(&self) -> u64 {
+        self.count_start + (self.interval - 1) / 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wraps_around() {
+        let generator = SequentialGenerator::new(5, 7);
+        let mut rng = SmallRng::seed_from_u64(0);
+        let values: Vec<_> = (0..7).map(|_| generator.next_value(&mut rng)).collect();
+        assert_eq!(values, vec![5, 6, 7, 5, 6, 7, 5]);
+    }
+}
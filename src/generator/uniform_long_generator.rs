@@ -7,12 +7,28 @@ pub struct UniformLongGenerator {
 }
 
 impl UniformLongGenerator {
-    pub fn new(lower_bound: u64, upper_bound: u64) -> Self {
+    /// Draw uniformly from `lower..=upper_inclusive`, e.g. scan lengths
+    /// from `1..=maxscanlength`
+    ///
+    /// # Panics
+    /// If `lower` is greater than `upper_inclusive`
+    pub fn new(lower: u64, upper_inclusive: u64) -> Self {
+        assert!(lower <= upper_inclusive);
         Self {
-            lower_bound,
-            upper_bound,
+            lower_bound: lower,
+            upper_bound: upper_inclusive,
         }
     }
+
+    /// Draw uniformly from `lower..upper_exclusive`, e.g. keys from
+    /// `0..recordcount`
+    ///
+    /// # Panics
+    /// If the range is empty
+    pub fn new_exclusive(lower: u64, upper_exclusive: u64) -> Self {
+        assert!(lower < upper_exclusive);
+        Self::new(lower, upper_exclusive - 1)
+    }
 }
 
 impl Generator<u64> for UniformLongGenerator {
@@ -23,6 +39,47 @@ impl Generator<u64> for UniformLongGenerator {
 
 impl NumberGenerator<u64> for UniformLongGenerator {
     fn mean(&self) -> u64 {
-        (self.lower_bound + self.upper_bound) / 2
+        self.lower_bound + (self.upper_bound - self.lower_bound) / 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draws(generator: &UniformLongGenerator) -> Vec<u64> {
+        let mut rng = SmallRng::seed_from_u64(0);
+        (0..10000).map(|_| generator.next_value(&mut rng)).collect()
+    }
+
+    #[test]
+    fn test_inclusive() {
+        let draws = draws(&UniformLongGenerator::new(1, 10));
+        assert!(draws.iter().all(|n| (1..=10).contains(n)));
+        assert!(draws.contains(&1));
+        assert!(draws.contains(&10));
+    }
+
+    #[test]
+    fn test_exclusive() {
+        let draws = draws(&UniformLongGenerator::new_exclusive(0, 10));
+        assert!(draws.iter().all(|n| (0..10).contains(n)));
+        assert!(draws.contains(&0));
+        assert!(draws.contains(&9));
+        assert_eq!(UniformLongGenerator::new_exclusive(5, 6).mean(), 5);
+    }
+
+    #[test]
+    fn test_mean_does_not_overflow() {
+        assert_eq!(
+            UniformLongGenerator::new(u64::MAX - 2, u64::MAX).mean(),
+            u64::MAX - 1
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_empty_range() {
+        UniformLongGenerator::new_exclusive(3, 3);
     }
 }
@@ -39,6 +39,16 @@ impl ZipfianGenerator {
         Self::from_range(0, items - 1)
     }
 
+    pub fn from_items_const(items: u64, zipfian_constant: f64) -> Self {
+        Self::from_range_const(0, items - 1, zipfian_constant)
+    }
+
+    /// Like [`ZipfianGenerator::from_items_const`], but reusing a `zeta`
+    /// from [`ZipfianGenerator::zeta`] instead of summing over all items
+    pub fn with_zeta(items: u64, zipfian_constant: f64, zeta: f64) -> Self {
+        Self::new(0, items - 1, zipfian_constant, zeta)
+    }
+
     pub fn from_range(min: u64, max: u64) -> Self {
         Self::from_range_const(min, max, ZIPFIAN_CONSTANT)
     }
@@ -73,6 +83,12 @@ impl ZipfianGenerator {
         }
     }
 
+    /// The zeta of the item count and zipfian constant, which is expensive
+    /// to compute for many items
+    pub fn zeta(&self) -> f64 {
+        self.zipfian_parameters.zetan
+    }
+
     fn next_long(&self, item_count: u64, rng: &mut SmallRng) -> u64 {
         if item_count != self.count_for_zeta {
             /*
@@ -147,4 +163,37 @@ mod tests {
         }
         println!("{:?}", result);
     }
+
+    #[test]
+    fn test_with_zeta() {
+        let zipfian = ZipfianGenerator::from_items_const(1000, 0.9);
+        assert_eq!(zipfian.zeta(), zeta_2(1000, 0.9));
+        let reused = ZipfianGenerator::with_zeta(1000, 0.9, zipfian.zeta());
+        assert_eq!(reused.zeta(), zipfian.zeta());
+        let mut a = SmallRng::seed_from_u64(0);
+        let mut b = SmallRng::seed_from_u64(0);
+        for _i in 0..1000 {
+            assert_eq!(zipfian.next_value(&mut a), reused.next_value(&mut b));
+        }
+    }
+
+    #[test]
+    fn test_matches_probability_mass() {
+        let items = 10;
+        let theta = 0.5;
+        let zipfian = ZipfianGenerator::from_items_const(items, theta);
+        let zetan = zeta_2(items, theta);
+        let mut counts = vec![0; items as usize];
+        let mut rng = SmallRng::seed_from_u64(0);
+        let draws = 1_000_000;
+        for _i in 0..draws {
+            counts[zipfian.next_value(&mut rng) as usize] += 1;
+        }
+        // the first two items are sampled exactly, the rest approximately
+        for (i, count) in counts.iter().take(2).enumerate() {
+            let expected = 1.0 / (i as f64 + 1.0).powf(theta) / zetan;
+            assert!((*count as f64 / draws as f64 - expected).abs() < 0.005);
+        }
+        assert!(counts.windows(2).all(|w| w[0] >= w[1]));
+    }
 }
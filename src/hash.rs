@@ -0,0 +1,239 @@
+use std::time::Duration;
+
+use crate::Workload;
+
+const FNV_OFFSET_BASIS_64: u64 = 0xcbf29ce484222325;
+const FNV_PRIME_64: u64 = 1099511628211;
+
+/// YCSB's 64-bit FNV-1a hash of the bytes of `key`, used to scatter keys
+/// (`Utils.fnvhash64`), including its final absolute value
+///
+/// See <http://en.wikipedia.org/wiki/Fowler_Noll_Vo_hash>
+pub fn fnv_hash64(key: u64) -> u64 {
+    let mut val = key;
+    let mut hashval = FNV_OFFSET_BASIS_64;
+    for _ in 0..8 {
+        let octet = val & 0x00ff;
+        val >>= 8;
+        hashval ^= octet;
+        hashval = hashval.wrapping_mul(FNV_PRIME_64);
+    }
+    (hashval as i64).wrapping_abs() as u64
+}
+
+/// FNV-1a over values encoded independently of the platform, so that the
+/// result is the same on every machine and in every process
+struct StableHasher(u64);
+
+impl StableHasher {
+    fn new() -> Self {
+        Self(FNV_OFFSET_BASIS_64)
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(FNV_PRIME_64);
+        }
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.u64(value as u64);
+    }
+
+    /// Hash the bits of `value`, treating `-0.0` as `0.0` as `==` does
+    fn f64(&mut self, value: f64) {
+        self.u64(if value == 0.0 { 0 } else { value.to_bits() });
+    }
+
+    fn str(&mut self, value: &str) {
+        self.u64(value.len() as u64);
+        self.bytes(value.as_bytes());
+    }
+
+    fn duration(&mut self, value: Duration) {
+        self.u64(value.as_secs());
+        self.u64(value.subsec_nanos() as u64);
+    }
+
+    fn option<T>(&mut self, value: Option<T>, f: impl FnOnce(&mut Self, T)) {
+        self.bool(value.is_some());
+        if let Some(value) = value {
+            f(self, value);
+        }
+    }
+
+    fn f64s(&mut self, values: &[f64]) {
+        self.u64(values.len() as u64);
+        for value in values {
+            self.f64(*value);
+        }
+    }
+}
+
+impl Workload {
+    /// A hash of every setting of the workload that is the same across
+    /// processes and machines, e.g. to cache benchmark results by
+    /// configuration
+    pub fn config_hash(&self) -> u64 {
+        // destructured so that new fields cannot be forgotten
+        let Workload {
+            workload,
+            record_count,
+            operation_count,
+            thread_count,
+            target,
+            insert_count,
+            insert_start,
+            field_count,
+            field_name_prefix,
+            field_length,
+            read_all_fields,
+            write_all_fields,
+            data_integrity,
+            field_length_distribution,
+            read_proportion,
+            update_proportion,
+            insert_proportion,
+            read_modify_write_proportion,
+            scan_proportion,
+            delete_proportion,
+            max_scan_length,
+            scan_length_distribution,
+            insert_order,
+            zero_padding,
+            request_distribution,
+            hotspot_data_fraction,
+            hotspot_operation_fraction,
+            max_execution_time,
+            insertion_retry_limit,
+            insertion_retry_interval,
+            status_interval,
+            table,
+            column_family,
+            measurement_type,
+            export_file,
+            export_measurements_interval,
+            seed,
+            histogram,
+            timeseries,
+            exponential,
+            zipfian,
+            scan,
+            hdrhistogram,
+            slo,
+            field_specs,
+        } = self;
+        let mut h = StableHasher::new();
+        h.str(workload);
+        for value in [
+            record_count,
+            operation_count,
+            thread_count,
+            target,
+            insert_count,
+            insert_start,
+            field_count,
+        ] {
+            h.u64(*value);
+        }
+        h.str(field_name_prefix);
+        h.u64(*field_length);
+        h.bool(*read_all_fields);
+        h.bool(*write_all_fields);
+        h.bool(*data_integrity);
+        h.str(field_length_distribution.as_str());
+        for value in [
+            read_proportion,
+            update_proportion,
+            insert_proportion,
+            read_modify_write_proportion,
+            scan_proportion,
+            delete_proportion,
+        ] {
+            h.f64(*value);
+        }
+        h.u64(*max_scan_length);
+        h.str(scan_length_distribution.as_str());
+        h.str(insert_order.as_str());
+        h.u64(*zero_padding);
+        h.str(request_distribution.as_str());
+        h.f64(*hotspot_data_fraction);
+        h.f64(*hotspot_operation_fraction);
+        h.duration(*max_execution_time);
+        h.u64(*insertion_retry_limit);
+        h.duration(*insertion_retry_interval);
+        h.duration(*status_interval);
+        h.str(table);
+        h.str(column_family);
+        h.str(measurement_type.as_str());
+        h.option(export_file.as_deref(), StableHasher::str);
+        h.duration(*export_measurements_interval);
+        h.option(*seed, StableHasher::u64);
+        h.duration(histogram.buckets);
+        h.f64s(&histogram.percentiles);
+        h.duration(timeseries.granularity);
+        h.f64(exponential.percentile);
+        h.f64(exponential.frac);
+        h.f64(zipfian.constant);
+        h.f64(scan.zipfian_constant);
+        h.f64s(&hdrhistogram.percentiles);
+        h.option(hdrhistogram.output_file.as_deref(), StableHasher::str);
+        for target in [
+            slo.read,
+            slo.update,
+            slo.insert,
+            slo.scan,
+            slo.read_modify_write,
+            slo.delete,
+        ] {
+            h.option(target, StableHasher::duration);
+        }
+        h.u64(field_specs.len() as u64);
+        for spec in field_specs {
+            h.u64(spec.length);
+            h.str(spec.length_distribution.as_str());
+        }
+        h.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Distribution, FieldSpec, WorkloadBuilder};
+
+    #[test]
+    fn matches_ycsb() {
+        // Utils.fnvhash64 of YCSB 0.17.0
+        assert_eq!(fnv_hash64(0), 6284781860667377211);
+        assert_eq!(fnv_hash64(1), 8517097267634966620);
+        assert_eq!(fnv_hash64(42), 55488592825689361);
+        assert_eq!(fnv_hash64(1000000), 1011632231655643464);
+    }
+
+    #[test]
+    fn config_hash() {
+        let a = Workload::a(1000, 1000);
+        assert_eq!(a.config_hash(), a.clone().config_hash());
+        assert_ne!(a.config_hash(), Workload::b(1000, 1000).config_hash());
+        assert_ne!(a.config_hash(), Workload::a(1000, 1001).config_hash());
+        let seeded = WorkloadBuilder::default().seed(0).build().unwrap();
+        assert_ne!(seeded.config_hash(), Workload::default().config_hash());
+        let mut negative_zero = Workload::a(1000, 1000);
+        negative_zero.scan_proportion = -0.0;
+        assert_eq!(negative_zero.config_hash(), a.config_hash());
+        let spec = WorkloadBuilder::default()
+            .field_count(1)
+            .field_specs(vec![FieldSpec::new(10, Distribution::Uniform)])
+            .build()
+            .unwrap();
+        let count = WorkloadBuilder::default().field_count(1).build().unwrap();
+        assert_ne!(spec.config_hash(), count.config_hash());
+        assert_ne!(spec.config_hash(), Workload::default().config_hash());
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{InsertOrder, Workload};
+
+/// Numbers the records inserted by the load phase according to the
+/// insert order, as an endless iterator of keys
+pub struct InsertKeyGenerator {
+    order: InsertOrder,
+    next: u64,
+}
+
+impl InsertKeyGenerator {
+    /// Yield keys for the records `start, start + 1, ...`, scattered by
+    /// hashing if `order` is hashed
+    pub fn new(order: InsertOrder, start: u64) -> Self {
+        Self { order, next: start }
+    }
+
+    /// Yield keys for the records inserted by the load phase of `workload`
+    pub fn from_workload(workload: &Workload) -> Self {
+        Self::new(workload.insert_order, workload.insert_start)
+    }
+}
+
+impl Iterator for InsertKeyGenerator {
+    type Item = u64;
+
+    /// The key of the next record, never `None`
+    fn next(&mut self) -> Option<u64> {
+        let keynum = self.next;
+        self.next += 1;
+        Some(self.order.key(keynum))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fnv_hash64;
+
+    #[test]
+    fn ordered() {
+        let keys: Vec<_> = InsertKeyGenerator::new(InsertOrder::Ordered, 10)
+            .take(3)
+            .collect();
+        assert_eq!(keys, [10, 11, 12]);
+    }
+
+    #[test]
+    fn hashed() {
+        let keys: Vec<_> = InsertKeyGenerator::new(InsertOrder::Hashed, 0)
+            .take(1000)
+            .collect();
+        assert_eq!(keys[0], fnv_hash64(0));
+        assert!(keys.windows(2).any(|w| w[0] > w[1]));
+        let mut unique = keys.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(unique.len(), keys.len());
+    }
+}
@@ -0,0 +1,768 @@
+//! Support for YCSB's native Java `.properties` workload files
+
+use std::{borrow::Cow, fmt::Display, io, num::ParseIntError, str::FromStr, time::Duration};
+
+use crate::{Distribution, FieldSpec, Operation, Workload, WorkloadError};
+
+/// Split a Java properties document into its `key=value` pairs.
+///
+/// Blank lines and lines starting with `#` or `!` are skipped. The key ends
+/// at the first `=`, `:` or whitespace, and surrounding whitespace is
+/// trimmed from both key and value.
+fn parse_lines(s: &str) -> impl Iterator<Item = Result<(&str, &str), WorkloadError>> {
+    s.lines()
+        .map(str::trim)
+        .filter(|line| !(line.is_empty() || line.starts_with('#') || line.starts_with('!')))
+        .map(|line| {
+            let (key, value) = match line.find(|c: char| c == '=' || c == ':' || c.is_whitespace())
+            {
+                Some(idx) => {
+                    let (key, rest) = line.split_at(idx);
+                    let rest = rest.trim_start();
+                    let rest = rest.strip_prefix(|c| c == '=' || c == ':').unwrap_or(rest);
+                    (key, rest.trim())
+                }
+                None => (line, ""),
+            };
+            if key.is_empty() {
+                return Err(WorkloadError::MalformedProperty(line.to_owned()));
+            }
+            Ok((key, value))
+        })
+}
+
+fn parse_value<T>(key: &str, value: &str) -> Result<T, WorkloadError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .parse()
+        .map_err(|e: T::Err| WorkloadError::InvalidValue {
+            key: key.to_owned(),
+            value: value.to_owned(),
+            reason: e.to_string(),
+        })
+}
+
+/// Like [`parse_value`], but calls out negative input explicitly rather
+/// than reporting it as an invalid digit. Used for counts and durations.
+fn parse_unsigned(key: &str, value: &str) -> Result<u64, WorkloadError> {
+    value.parse().map_err(|e: ParseIntError| {
+        let reason = if value.starts_with('-') {
+            "expected a non-negative integer".to_owned()
+        } else {
+            e.to_string()
+        };
+        WorkloadError::InvalidValue {
+            key: key.to_owned(),
+            value: value.to_owned(),
+            reason,
+        }
+    })
+}
+
+/// Like [`parse_value`], but ignoring case and surrounding whitespace, as
+/// hand-written files contain e.g. `requestdistribution = Zipfian`
+fn parse_distribution(key: &str, value: &str) -> Result<Distribution, WorkloadError> {
+    parse_value(key, &value.trim().to_ascii_lowercase()).map_err(|e| match e {
+        WorkloadError::InvalidValue { key, reason, .. } => WorkloadError::InvalidValue {
+            key,
+            value: value.to_owned(),
+            reason,
+        },
+        e => e,
+    })
+}
+
+/// The index and attribute of a `field.{index}.length` or
+/// `field.{index}.lengthdistribution` property
+fn field_spec_property(key: &str) -> Option<(usize, &str)> {
+    let (index, attribute) = key.strip_prefix("field.")?.split_once('.')?;
+    let index = index.parse().ok()?;
+    matches!(attribute, "length" | "lengthdistribution").then_some((index, attribute))
+}
+
+/// A comma-separated list of values, where an empty `value` is an empty
+/// list as written for e.g. `hdrhistogram.percentiles` without entries
+fn parse_list<T>(key: &str, value: &str) -> Result<Vec<T>, WorkloadError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    if value.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    value
+        .split(',')
+        .map(|item| parse_value(key, item.trim()))
+        .collect()
+}
+
+/// The property holding the p99 latency objective of each operation
+const SLO_PROPERTIES: [(&str, Operation); 6] = [
+    ("slo.read", Operation::Read),
+    ("slo.update", Operation::Update),
+    ("slo.insert", Operation::Insert),
+    ("slo.scan", Operation::Scan),
+    ("slo.readmodifywrite", Operation::ReadModifyWrite),
+    ("slo.delete", Operation::Delete),
+];
+
+fn format_list<T: ToString>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl Workload {
+    /// Parse a workload from YCSB's Java `.properties` format.
+    ///
+    /// Properties not set in `s` keep their default value. Keys that do not
+    /// correspond to a workload property are reported through
+    /// [`WorkloadError::UnknownProperties`].
+    pub fn from_properties_str(s: &str) -> Result<Self, WorkloadError> {
+        let mut workload = Workload::default();
+        let mut unknown = vec![];
+        for line in parse_lines(s) {
+            let (key, value) = line?;
+            if !workload.set_property(key, value)? {
+                unknown.push(key.to_owned());
+            }
+        }
+        if !unknown.is_empty() {
+            return Err(WorkloadError::UnknownProperties(unknown));
+        }
+        Ok(workload)
+    }
+
+    /// Parse a workload in YCSB's Java `.properties` format from `reader`,
+    /// see [`Workload::from_properties_str`]
+    pub fn from_properties_reader<R: io::Read>(mut reader: R) -> Result<Self, WorkloadError> {
+        let mut s = String::new();
+        reader.read_to_string(&mut s)?;
+        Workload::from_properties_str(&s)
+    }
+
+    /// Apply YCSB-style `-p key=value` overrides, parsing each value as
+    /// [`Workload::from_properties_str`] would.
+    ///
+    /// The workload is left untouched if any override has an unknown key
+    /// or an invalid value.
+    pub fn apply_overrides(&mut self, overrides: &[(String, String)]) -> Result<(), WorkloadError> {
+        self.apply_overrides_iter(overrides.iter().map(|(key, value)| (key, value)))
+    }
+
+    /// Like [`Workload::apply_overrides`], but taking the overrides one at
+    /// a time, e.g. as they are read from a file, without collecting them
+    pub fn apply_overrides_iter<I, K, V>(&mut self, overrides: I) -> Result<(), WorkloadError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut workload = self.clone();
+        let mut unknown = vec![];
+        for (key, value) in overrides {
+            let key = key.as_ref();
+            if !workload.set_property(key.trim(), value.as_ref().trim())? {
+                unknown.push(key.to_owned());
+            }
+        }
+        if !unknown.is_empty() {
+            return Err(WorkloadError::UnknownProperties(unknown));
+        }
+        *self = workload;
+        Ok(())
+    }
+
+    /// Render the workload in YCSB's Java `.properties` format, one
+    /// `key=value` line per property.
+    pub fn to_properties_string(&self) -> String {
+        self.properties()
+            .into_iter()
+            .map(|(key, value)| format!("{}={}\n", key, value))
+            .collect()
+    }
+
+    /// The workload as arguments to YCSB's command line, a `-p key=value`
+    /// pair per property in the units [`Workload::to_properties_string`]
+    /// uses, e.g. to run the Java YCSB with [`std::process::Command`]
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.properties()
+            .into_iter()
+            .flat_map(|(key, value)| ["-p".to_owned(), format!("{}={}", key, value)])
+            .collect()
+    }
+
+    /// Every property of the workload as a YCSB property name and value,
+    /// in the units upstream YCSB expects.
+    pub(crate) fn properties(&self) -> Vec<(Cow<'static, str>, String)> {
+        let properties = vec![
+            ("workload", self.workload.clone()),
+            ("recordcount", self.record_count.to_string()),
+            ("operationcount", self.operation_count.to_string()),
+            ("threadcount", self.thread_count.to_string()),
+            ("target", self.target.to_string()),
+            ("insertcount", self.insert_count.to_string()),
+            ("insertstart", self.insert_start.to_string()),
+            ("fieldcount", self.field_count.to_string()),
+            ("fieldnameprefix", self.field_name_prefix.clone()),
+            ("fieldlength", self.field_length.to_string()),
+            ("readallfields", self.read_all_fields.to_string()),
+            ("writeallfields", self.write_all_fields.to_string()),
+            ("dataintegrity", self.data_integrity.to_string()),
+            (
+                "fieldlengthdistribution",
+                self.field_length_distribution.to_string(),
+            ),
+            ("readproportion", self.read_proportion.to_string()),
+            ("updateproportion", self.update_proportion.to_string()),
+            ("insertproportion", self.insert_proportion.to_string()),
+            (
+                "readmodifywriteproportion",
+                self.read_modify_write_proportion.to_string(),
+            ),
+            ("scanproportion", self.scan_proportion.to_string()),
+            ("deleteproportion", self.delete_proportion.to_string()),
+            ("maxscanlength", self.max_scan_length.to_string()),
+            (
+                "scanlengthdistribution",
+                self.scan_length_distribution.to_string(),
+            ),
+            ("insertorder", self.insert_order.to_string()),
+            ("zeropadding", self.zero_padding.to_string()),
+            ("requestdistribution", self.request_distribution.to_string()),
+            (
+                "hotspotdatafraction",
+                self.hotspot_data_fraction.to_string(),
+            ),
+            (
+                "hotspotopnfraction",
+                self.hotspot_operation_fraction.to_string(),
+            ),
+            (
+                "maxexecutiontime",
+                self.max_execution_time.as_secs().to_string(),
+            ),
+            (
+                "core_workload_insertion_retry_limit",
+                self.insertion_retry_limit.to_string(),
+            ),
+            (
+                "core_workload_insertion_retry_interval",
+                self.insertion_retry_interval.as_secs().to_string(),
+            ),
+            (
+                "status.interval",
+                self.status_interval.as_secs().to_string(),
+            ),
+            ("table", self.table.clone()),
+            ("columnfamily", self.column_family.clone()),
+            ("measurementtype", self.measurement_type.to_string()),
+            (
+                "exportmeasurementsinterval",
+                self.export_measurements_interval.as_secs().to_string(),
+            ),
+            (
+                "histogram.buckets",
+                self.histogram.buckets.as_millis().to_string(),
+            ),
+            (
+                "timeseries.granularity",
+                self.timeseries.granularity.as_millis().to_string(),
+            ),
+            (
+                "exponential.percentile",
+                self.exponential.percentile.to_string(),
+            ),
+            ("exponential.frac", self.exponential.frac.to_string()),
+            ("zipfian.constant", self.zipfian.constant.to_string()),
+            (
+                "scan.zipfianconstant",
+                self.scan.zipfian_constant.to_string(),
+            ),
+            (
+                "hdrhistogram.percentiles",
+                format_list(&self.hdrhistogram.percentiles),
+            ),
+        ];
+        let mut properties: Vec<_> = properties
+            .into_iter()
+            .map(|(key, value)| (Cow::Borrowed(key), value))
+            .collect();
+        if !self.histogram.percentiles.is_empty() {
+            properties.push((
+                "histogram.percentiles".into(),
+                format_list(&self.histogram.percentiles),
+            ));
+        }
+        if let Some(output_file) = &self.hdrhistogram.output_file {
+            properties.push(("hdrhistogram.outputfile".into(), output_file.clone()));
+        }
+        if let Some(export_file) = &self.export_file {
+            properties.push(("exportfile".into(), export_file.clone()));
+        }
+        if let Some(seed) = self.seed {
+            properties.push(("seed".into(), seed.to_string()));
+        }
+        for (key, operation) in SLO_PROPERTIES {
+            if let Some(latency) = self.slo.p99(operation) {
+                properties.push((key.into(), latency.as_millis().to_string()));
+            }
+        }
+        for (index, spec) in self.field_specs.iter().enumerate() {
+            properties.push((
+                format!("field.{}.length", index).into(),
+                spec.length.to_string(),
+            ));
+            properties.push((
+                format!("field.{}.lengthdistribution", index).into(),
+                spec.length_distribution.to_string(),
+            ));
+        }
+        properties
+    }
+
+    /// Set the field corresponding to the YCSB property `key`.
+    /// Returns `false` if `key` is not a known property.
+    fn set_property(&mut self, key: &str, value: &str) -> Result<bool, WorkloadError> {
+        match key {
+            "workload" => self.workload = value.to_owned(),
+            "recordcount" => self.record_count = parse_unsigned(key, value)?,
+            "operationcount" => self.operation_count = parse_unsigned(key, value)?,
+            "threadcount" => self.thread_count = parse_unsigned(key, value)?,
+            "target" => self.target = parse_unsigned(key, value)?,
+            "insertcount" => self.insert_count = parse_unsigned(key, value)?,
+            "insertstart" => self.insert_start = parse_unsigned(key, value)?,
+            "fieldcount" => self.field_count = parse_unsigned(key, value)?,
+            "fieldnameprefix" => self.field_name_prefix = value.to_owned(),
+            "fieldlength" => self.field_length = parse_unsigned(key, value)?,
+            "readallfields" => self.read_all_fields = parse_value(key, value)?,
+            "writeallfields" => self.write_all_fields = parse_value(key, value)?,
+            "dataintegrity" => self.data_integrity = parse_value(key, value)?,
+            "fieldlengthdistribution" => {
+                self.field_length_distribution = parse_distribution(key, value)?
+            }
+            "readproportion" => self.read_proportion = parse_value(key, value)?,
+            "updateproportion" => self.update_proportion = parse_value(key, value)?,
+            "insertproportion" => self.insert_proportion = parse_value(key, value)?,
+            "readmodifywriteproportion" => {
+                self.read_modify_write_proportion = parse_value(key, value)?
+            }
+            "scanproportion" => self.scan_proportion = parse_value(key, value)?,
+            "deleteproportion" => self.delete_proportion = parse_value(key, value)?,
+            "maxscanlength" => self.max_scan_length = parse_unsigned(key, value)?,
+            "scanlengthdistribution" => {
+                self.scan_length_distribution = parse_distribution(key, value)?
+            }
+            "insertorder" => self.insert_order = parse_value(key, value)?,
+            "zeropadding" => self.zero_padding = parse_unsigned(key, value)?,
+            "requestdistribution" => self.request_distribution = parse_distribution(key, value)?,
+            "hotspotdatafraction" => self.hotspot_data_fraction = parse_value(key, value)?,
+            "hotspotopnfraction" => self.hotspot_operation_fraction = parse_value(key, value)?,
+            "maxexecutiontime" => {
+                self.max_execution_time = Duration::from_secs(parse_unsigned(key, value)?)
+            }
+            "core_workload_insertion_retry_limit" => {
+                self.insertion_retry_limit = parse_unsigned(key, value)?
+            }
+            "core_workload_insertion_retry_interval" => {
+                self.insertion_retry_interval = Duration::from_secs(parse_unsigned(key, value)?)
+            }
+            "status.interval" | "statusinterval" => {
+                self.status_interval = Duration::from_secs(parse_unsigned(key, value)?)
+            }
+            "table" => self.table = value.to_owned(),
+            "columnfamily" => self.column_family = value.to_owned(),
+            "measurementtype" => self.measurement_type = parse_value(key, value)?,
+            "exportfile" => self.export_file = Some(value.to_owned()),
+            "exportmeasurementsinterval" => {
+                self.export_measurements_interval = Duration::from_secs(parse_unsigned(key, value)?)
+            }
+            "seed" => self.seed = Some(parse_unsigned(key, value)?),
+            "histogram.buckets" => {
+                self.histogram.buckets = Duration::from_millis(parse_unsigned(key, value)?)
+            }
+            "histogram.percentiles" => self.histogram.percentiles = parse_list(key, value)?,
+            "timeseries.granularity" => {
+                self.timeseries.granularity = Duration::from_millis(parse_unsigned(key, value)?)
+            }
+            "exponential.percentile" => self.exponential.percentile = parse_value(key, value)?,
+            "exponential.frac" => self.exponential.frac = parse_value(key, value)?,
+            "zipfian.constant" | "zipfianconstant" => {
+                self.zipfian.constant = parse_value(key, value)?
+            }
+            "scan.zipfianconstant" => self.scan.zipfian_constant = parse_value(key, value)?,
+            "hdrhistogram.percentiles" => self.hdrhistogram.percentiles = parse_list(key, value)?,
+            "hdrhistogram.outputfile" => self.hdrhistogram.output_file = Some(value.to_owned()),
+            _ => {
+                if let Some(&(_, operation)) =
+                    SLO_PROPERTIES.iter().find(|(slo_key, _)| *slo_key == key)
+                {
+                    *self.slo.target_mut(operation) =
+                        Some(Duration::from_millis(parse_unsigned(key, value)?))
+                } else if let Some((index, attribute)) = field_spec_property(key) {
+                    self.set_field_spec_property(key, index, attribute, value)?
+                } else {
+                    return Ok(false);
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// Set `attribute` of the `[[field]]` table `index` from the property
+    /// `field.{index}.{attribute}`. Tables must be given in order; a new one
+    /// starts out with `fieldlength` and a constant distribution.
+    fn set_field_spec_property(
+        &mut self,
+        key: &str,
+        index: usize,
+        attribute: &str,
+        value: &str,
+    ) -> Result<(), WorkloadError> {
+        if index > self.field_specs.len() {
+            return Err(WorkloadError::InvalidValue {
+                key: key.to_owned(),
+                value: value.to_owned(),
+                reason: format!("field.{}.* is not set", self.field_specs.len()),
+            });
+        }
+        if index == self.field_specs.len() {
+            self.field_specs
+                .push(FieldSpec::new(self.field_length, Distribution::Constant));
+        }
+        let spec = &mut self.field_specs[index];
+        match attribute {
+            "length" => spec.length = parse_unsigned(key, value)?,
+            _ => spec.length_distribution = parse_distribution(key, value)?,
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Distribution, ExponentialConfig, HdrHistogramConfig, HistogramConfig, InsertOrder,
+        MeasurementType, ScanConfig, SloConfig, TimeseriesConfig, WorkloadBuilder, ZipfianConfig,
+    };
+
+    const WORKLOAD_A: &str = r#"
+# Yahoo! Cloud System Benchmark
+# Workload A: Update heavy workload
+recordcount=1000
+operationcount=1000
+workload=core
+
+readallfields=true
+
+readproportion=0.5
+updateproportion=0.5
+scanproportion=0
+insertproportion=0
+
+! alternative comment style
+requestdistribution = uniform
+"#;
+
+    #[test]
+    fn workloada() {
+        assert_eq!(
+            Workload::from_properties_str(WORKLOAD_A).unwrap(),
+            Workload::a(1000, 1000)
+        );
+        assert_eq!(
+            Workload::from_properties_reader(WORKLOAD_A.as_bytes()).unwrap(),
+            Workload::a(1000, 1000)
+        );
+    }
+
+    #[test]
+    fn separators() {
+        let config =
+            Workload::from_properties_str("recordcount 10\noperationcount: 20\ntable =t").unwrap();
+        let expected = WorkloadBuilder::default()
+            .record_count(10)
+            .operation_count(20)
+            .table("t".to_owned())
+            .build()
+            .unwrap();
+        assert_eq!(config, expected);
+    }
+
+    #[test]
+    fn nested_properties() {
+        let config = Workload::from_properties_str(
+            "histogram.buckets=500\ntimeseries.granularity=10\nmaxexecutiontime=60",
+        )
+        .unwrap();
+        assert_eq!(config.histogram.buckets, Duration::from_millis(500));
+        assert_eq!(config.timeseries.granularity, Duration::from_millis(10));
+        assert_eq!(config.max_execution_time, Duration::from_secs(60));
+    }
+
+    #[test]
+    fn round_trip() {
+        let config = WorkloadBuilder::default()
+            .workload("site.ycsb.workloads.CoreWorkload".to_owned())
+            .record_count(12)
+            .operation_count(34)
+            .thread_count(5)
+            .target(100)
+            .insert_count(6)
+            .insert_start(7)
+            .field_count(8)
+            .field_name_prefix("col".to_owned())
+            .field_length(9)
+            .read_all_fields(false)
+            .write_all_fields(true)
+            .data_integrity(true)
+            .field_length_distribution(Distribution::Zipfian)
+            .read_proportion(0.1)
+            .update_proportion(0.2)
+            .insert_proportion(0.3)
+            .read_modify_write_proportion(0.15)
+            .scan_proportion(0.2)
+            .delete_proportion(0.05)
+            .max_scan_length(100)
+            .scan_length_distribution(Distribution::Zipfian)
+            .insert_order(InsertOrder::Ordered)
+            .zero_padding(12)
+            .request_distribution(Distribution::Latest)
+            .hotspot_data_fraction(0.3)
+            .hotspot_operation_fraction(0.7)
+            .max_execution_time(Duration::from_secs(600))
+            .insertion_retry_limit(5)
+            .insertion_retry_interval(Duration::from_secs(10))
+            .status_interval(Duration::from_secs(1))
+            .table("t".to_owned())
+            .column_family("cf".to_owned())
+            .measurement_type(MeasurementType::Timeseries)
+            .export_file("out.txt".to_owned())
+            .export_measurements_interval(Duration::from_secs(30))
+            .seed(42)
+            .histogram(HistogramConfig {
+                buckets: Duration::from_millis(200),
+                percentiles: vec![50., 99.],
+            })
+            .timeseries(TimeseriesConfig {
+                granularity: Duration::from_millis(50),
+            })
+            .exponential(ExponentialConfig {
+                percentile: 90.,
+                frac: 0.25,
+            })
+            .zipfian(ZipfianConfig { constant: 0.5 })
+            .scan(ScanConfig {
+                zipfian_constant: 0.8,
+            })
+            .hdrhistogram(HdrHistogramConfig {
+                percentiles: vec![90., 99.99],
+                output_file: Some("hdr.txt".to_owned()),
+            })
+            .slo(SloConfig::default().with_p99(Operation::Read, Duration::from_millis(10)))
+            .field_specs(
+                (1..=8)
+                    .map(|length| FieldSpec::new(length, Distribution::Uniform))
+                    .collect(),
+            )
+            .build()
+            .unwrap();
+        let properties = config.to_properties_string();
+        assert!(properties.contains("maxexecutiontime=600\n"));
+        assert!(properties.contains("histogram.buckets=200\n"));
+        assert!(properties.contains("requestdistribution=latest\n"));
+        assert!(properties.contains("slo.read=10\n"));
+        assert!(properties.contains("field.7.length=8\nfield.7.lengthdistribution=uniform\n"));
+        assert_eq!(Workload::from_properties_str(&properties).unwrap(), config);
+        assert!(matches!(
+            Workload::from_properties_str("field.1.length=5"),
+            Err(WorkloadError::InvalidValue { key, .. }) if key == "field.1.length"
+        ));
+        assert!(matches!(
+            Workload::from_properties_str("field.0.width=5"),
+            Err(WorkloadError::UnknownProperties(_))
+        ));
+        assert_eq!(
+            Workload::from_properties_str(&Workload::default().to_properties_string()).unwrap(),
+            Workload::default()
+        );
+    }
+
+    #[test]
+    fn empty_percentiles() {
+        let config = WorkloadBuilder::default()
+            .hdrhistogram(HdrHistogramConfig {
+                percentiles: vec![],
+                output_file: None,
+            })
+            .build()
+            .unwrap();
+        let properties = config.to_properties_string();
+        assert!(properties.contains("hdrhistogram.percentiles=\n"));
+        assert_eq!(Workload::from_properties_str(&properties).unwrap(), config);
+        assert!(matches!(
+            Workload::from_properties_str("histogram.percentiles=50,,99"),
+            Err(WorkloadError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn to_cli_args() {
+        let config = WorkloadBuilder::default()
+            .max_execution_time(Duration::from_secs(60))
+            .build()
+            .unwrap();
+        let args = config.to_cli_args();
+        assert_eq!(args[..2], ["-p", "workload=core"]);
+        assert!(args.chunks(2).all(|pair| pair[0] == "-p"));
+        assert!(args.contains(&"maxexecutiontime=60".to_owned()));
+        assert!(args.contains(&"histogram.buckets=1000".to_owned()));
+        let overrides: Vec<_> = args
+            .chunks(2)
+            .map(|pair| {
+                let (key, value) = pair[1].split_once('=').unwrap();
+                (key.to_owned(), value.to_owned())
+            })
+            .collect();
+        let mut parsed = Workload::default();
+        parsed.apply_overrides(&overrides).unwrap();
+        assert_eq!(parsed, config);
+    }
+
+    #[test]
+    fn unknown_properties() {
+        match Workload::from_properties_str("recordcount=1\ncassandra.hosts=localhost\nfoo=bar") {
+            Err(WorkloadError::UnknownProperties(keys)) => {
+                assert_eq!(keys, vec!["cassandra.hosts", "foo"])
+            }
+            r => panic!("unexpected result {:?}", r),
+        }
+    }
+
+    #[test]
+    fn invalid_values() {
+        assert!(matches!(
+            Workload::from_properties_str("recordcount=many"),
+            Err(WorkloadError::InvalidValue { key, .. }) if key == "recordcount"
+        ));
+        match Workload::from_properties_str("recordcount=-1") {
+            Err(WorkloadError::InvalidValue { key, value, reason }) => {
+                assert_eq!(key, "recordcount");
+                assert_eq!(value, "-1");
+                assert_eq!(reason, "expected a non-negative integer");
+            }
+            r => panic!("unexpected result {:?}", r),
+        }
+        assert!(matches!(
+            Workload::from_properties_str("fieldcount=1.5"),
+            Err(WorkloadError::InvalidValue { key, .. }) if key == "fieldcount"
+        ));
+        assert!(matches!(
+            Workload::from_properties_str("requestdistribution=gaussian"),
+            Err(WorkloadError::InvalidValue { key, .. }) if key == "requestdistribution"
+        ));
+        assert_eq!(
+            Workload::from_properties_str("requestdistribution=latest")
+                .unwrap()
+                .request_distribution,
+            Distribution::Latest
+        );
+    }
+
+    #[test]
+    fn mixed_case_distributions() {
+        let config = Workload::from_properties_str(
+            "requestdistribution = Zipfian\n\
+             fieldlengthdistribution=UNIFORM\n\
+             scanlengthdistribution:\tScrambledZipfian ",
+        )
+        .unwrap();
+        assert_eq!(config.request_distribution, Distribution::Zipfian);
+        assert_eq!(config.field_length_distribution, Distribution::Uniform);
+        assert_eq!(
+            config.scan_length_distribution,
+            Distribution::ScrambledZipfian
+        );
+        let mut config = Workload::default();
+        config
+            .apply_overrides_iter([("requestdistribution", " HotSpot ")])
+            .unwrap();
+        assert_eq!(config.request_distribution, Distribution::Hotspot);
+        assert!(matches!(
+            Workload::from_properties_str("requestdistribution=Gaussian"),
+            Err(WorkloadError::InvalidValue { value, .. }) if value == "Gaussian"
+        ));
+    }
+
+    #[test]
+    fn apply_overrides() {
+        let mut config = Workload::a(1000, 1000);
+        config
+            .apply_overrides(&[
+                ("readproportion".to_owned(), "0.8".to_owned()),
+                ("updateproportion".to_owned(), "0.2".to_owned()),
+            ])
+            .unwrap();
+        assert_eq!(config.read_proportion, 0.8);
+        assert_eq!(config.update_proportion, 0.2);
+        let before = config.clone();
+        assert!(matches!(
+            config.apply_overrides(&[
+                ("recordcount".to_owned(), "10".to_owned()),
+                ("nosuchkey".to_owned(), "1".to_owned()),
+            ]),
+            Err(WorkloadError::UnknownProperties(keys)) if keys == ["nosuchkey"]
+        ));
+        assert_eq!(config, before);
+    }
+
+    #[test]
+    fn negative_durations() {
+        for key in [
+            "maxexecutiontime",
+            "status.interval",
+            "histogram.buckets",
+            "timeseries.granularity",
+        ] {
+            let properties = format!("{}=-5", key);
+            match Workload::from_properties_str(&properties) {
+                Err(WorkloadError::InvalidValue { key: k, reason, .. }) => {
+                    assert_eq!(k, key);
+                    assert_eq!(reason, "expected a non-negative integer");
+                }
+                r => panic!("unexpected result {:?}", r),
+            }
+            let mut config = Workload::default();
+            assert!(matches!(
+                config.apply_overrides_iter([(key, "-5")]),
+                Err(WorkloadError::InvalidValue { .. })
+            ));
+            assert_eq!(config, Workload::default());
+        }
+    }
+
+    #[test]
+    fn apply_overrides_iter() {
+        let mut config = Workload::a(1000, 1000);
+        let lines = "recordcount=10\noperationcount=20\n";
+        config
+            .apply_overrides_iter(lines.lines().filter_map(|line| line.split_once('=')))
+            .unwrap();
+        assert_eq!(config.record_count, 10);
+        assert_eq!(config.operation_count, 20);
+        let overrides = (0..1000).map(|i| ("seed".to_owned(), i.to_string()));
+        config.apply_overrides_iter(overrides).unwrap();
+        assert_eq!(config.seed, Some(999));
+        assert!(matches!(
+            config.apply_overrides_iter([("recordcount", "-1")]),
+            Err(WorkloadError::InvalidValue { .. })
+        ));
+        assert_eq!(config.record_count, 10);
+    }
+}
@@ -0,0 +1,236 @@
+use rand::{rngs::SmallRng, SeedableRng};
+
+use crate::{
+    generator::{
+        ConstantGenerator, ExponentialGenerator, Generator, HotspotIntegerGenerator,
+        ScrambledZipfianGenerator, SequentialGenerator, UniformLongGenerator, ZipfianGenerator,
+    },
+    Distribution, ExponentialConfig, InsertOrder, Workload, ZipfianConfig,
+};
+
+/// Chooses which record a transaction operates on according to the
+/// request distribution
+pub struct KeyChooser {
+    rng: SmallRng,
+    generator: Box<dyn Generator<u64> + Send>,
+    /// Whether `generator` yields offsets back from the latest inserted key
+    /// rather than keys
+    from_latest: bool,
+    /// The record number of the latest inserted record
+    latest: u64,
+    /// How record numbers map to the keys written by inserts
+    insert_order: InsertOrder,
+}
+
+impl KeyChooser {
+    pub fn new(distribution: Distribution, record_count: u64, rng_seed: u64) -> Self {
+        let defaults = Workload::default();
+        Self::with_config(
+            distribution,
+            record_count,
+            ZipfianConfig::default(),
+            ExponentialConfig::default(),
+            (
+                defaults.hotspot_data_fraction,
+                defaults.hotspot_operation_fraction,
+            ),
+            defaults.insert_order,
+            SmallRng::seed_from_u64(rng_seed),
+        )
+    }
+
+    /// Choose keys among the records of `workload` according to its
+    /// request distribution and distribution parameters, seeded by
+    /// [`Workload::rng`]
+    ///
+    /// # Panics
+    /// If `record_count` is zero, which [`Workload::validate`] rejects
+    /// unless the workload only inserts
+    pub fn from_workload(workload: &Workload) -> Self {
+        Self::with_config(
+            workload.request_distribution,
+            workload.record_count,
+            workload.zipfian,
+            workload.exponential,
+            (
+                workload.hotspot_data_fraction,
+                workload.hotspot_operation_fraction,
+            ),
+            workload.insert_order,
+            workload.rng(),
+        )
+    }
+
+    fn with_config(
+        distribution: Distribution,
+        record_count: u64,
+        zipfian: ZipfianConfig,
+        exponential: ExponentialConfig,
+        (hot_data_fraction, hot_operation_fraction): (f64, f64),
+        insert_order: InsertOrder,
+        rng: SmallRng,
+    ) -> Self {
+        assert!(record_count > 0);
+        let (generator, from_latest): (Box<dyn Generator<u64> + Send>, _) = match distribution {
+            Distribution::Constant => (Box::new(ConstantGenerator::new(0)), false),
+            Distribution::Uniform => (
+                Box::new(UniformLongGenerator::new_exclusive(0, record_count)),
+                false,
+            ),
+            Distribution::Zipfian => (
+                Box::new(ZipfianGenerator::from_items_const(
+                    record_count,
+                    zipfian.constant,
+                )),
+                false,
+            ),
+            Distribution::Latest => (Box::new(ZipfianGenerator::from_items(record_count)), true),
+            Distribution::Exponential => (
+                Box::new(ExponentialGenerator::new(
+                    exponential.percentile,
+                    record_count as f64 * exponential.frac,
+                )),
+                true,
+            ),
+            Distribution::ScrambledZipfian => (
+                Box::new(ScrambledZipfianGenerator::from_items(record_count)),
+                false,
+            ),
+            Distribution::Sequential => (
+                Box::new(SequentialGenerator::new(0, record_count - 1)),
+                false,
+            ),
+            // keys in `Workload::hot_set_range` are chosen with probability
+            // `hot_operation_fraction`, the rest uniformly from the cold set
+            Distribution::Hotspot => (
+                Box::new(HotspotIntegerGenerator::new(
+                    0,
+                    record_count - 1,
+                    hot_data_fraction,
+                    hot_operation_fraction,
+                )),
+                false,
+            ),
+        };
+        Self {
+            rng,
+            generator,
+            from_latest,
+            latest: record_count - 1,
+            insert_order,
+        }
+    }
+
+    pub fn next_key(&mut self) -> u64 {
+        if !self.from_latest {
+            return self.generator.next_value(&mut self.rng);
+        }
+        loop {
+            let offset = self.generator.next_value(&mut self.rng);
+            if offset <= self.latest {
+                return self.latest - offset;
+            }
+        }
+    }
+
+    /// Record that record number `key` has been inserted, making it
+    /// eligible for selection by the latest distribution
+    pub fn acknowledge_insert(&mut self, key: u64) {
+        self.latest = self.latest.max(key);
+    }
+
+    /// The key of the most recently inserted record as written by the
+    /// insert, i.e. scattered by hashing if the insert order is hashed
+    pub fn latest_key(&self) -> u64 {
+        self.insert_order.key(self.latest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fnv_hash64, InsertKeyGenerator, WorkloadBuilder};
+
+    #[test]
+    fn keys_within_range() {
+        for distribution in [
+            Distribution::Uniform,
+            Distribution::Zipfian,
+            Distribution::Latest,
+            Distribution::Exponential,
+            Distribution::ScrambledZipfian,
+            Distribution::Sequential,
+            Distribution::Hotspot,
+        ] {
+            let mut chooser = KeyChooser::new(distribution, 100, 0);
+            for _ in 0..10000 {
+                assert!(chooser.next_key() < 100, "{}", distribution);
+            }
+        }
+        let mut chooser = KeyChooser::new(Distribution::Constant, 100, 0);
+        assert_eq!(chooser.next_key(), 0);
+    }
+
+    #[test]
+    fn seeded_workload_is_deterministic() {
+        let workload = WorkloadBuilder::default()
+            .record_count(1000)
+            .seed(42)
+            .build()
+            .unwrap();
+        let mut a = KeyChooser::from_workload(&workload);
+        let mut b = KeyChooser::from_workload(&workload);
+        let a: Vec<_> = (0..1000).map(|_| a.next_key()).collect();
+        let b: Vec<_> = (0..1000).map(|_| b.next_key()).collect();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn latest_follows_inserts() {
+        let mut chooser = KeyChooser::new(Distribution::Latest, 100, 0);
+        chooser.acknowledge_insert(1000);
+        let hits = (0..10000).filter(|_| chooser.next_key() > 900).count();
+        assert!(hits > 5000);
+    }
+
+    #[test]
+    fn latest_key_follows_insert_order() {
+        for (insert_order, expected) in [
+            (InsertOrder::Ordered, 104),
+            (InsertOrder::Hashed, fnv_hash64(104)),
+        ] {
+            let workload = WorkloadBuilder::default()
+                .record_count(100)
+                .request_distribution(Distribution::Latest)
+                .insert_order(insert_order)
+                .build()
+                .unwrap();
+            let mut chooser = KeyChooser::from_workload(&workload);
+            let mut keys = InsertKeyGenerator::new(insert_order, 99);
+            assert_eq!(Some(chooser.latest_key()), keys.next());
+            for key in 100..105 {
+                chooser.acknowledge_insert(key);
+            }
+            assert_eq!(chooser.latest_key(), expected);
+        }
+    }
+
+    #[test]
+    fn hotspot_prefers_hot_set() {
+        let workload = WorkloadBuilder::default()
+            .record_count(1000)
+            .request_distribution(Distribution::Hotspot)
+            .hotspot_data_fraction(0.1)
+            .hotspot_operation_fraction(0.9)
+            .seed(0)
+            .build()
+            .unwrap();
+        let mut chooser = KeyChooser::from_workload(&workload);
+        let hot_set = workload.hot_set_range();
+        assert_eq!(hot_set, 0..100);
+        let hot = (0..10000)
+            .filter(|_| hot_set.contains(&chooser.next_key()))
+            .count();
+        assert!((8800..9200).contains(&hot));
+    }
+}
@@ -1,53 +1,705 @@
-use std::{fs, path::Path, time::Duration};
+use std::{fmt, io, ops::Range, path::PathBuf, str::FromStr, time::Duration};
+#[cfg(feature = "serde")]
+use std::{fs, path::Path};
 
 use derive_builder::Builder;
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use serde_with::{serde_as, DurationMilliSeconds, DurationSeconds};
+#[cfg(feature = "serde")]
+use serde_with::{As, DurationMilliSeconds, DurationSeconds};
+use thiserror::Error;
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-#[serde(rename_all = "lowercase")]
+mod diff;
+mod field_length_generator;
+#[cfg(feature = "serde")]
+mod format;
+pub mod generator;
+mod hash;
+mod insert_key_generator;
+mod java_properties;
+mod key_chooser;
+pub mod measurement;
+mod operation;
+mod operation_proportions;
+mod partial_workload;
+mod runner;
+mod standard_workload;
+mod trace;
+
+pub use diff::FieldDiff;
+pub use field_length_generator::FieldLengthGenerator;
+pub use hash::fnv_hash64;
+pub use insert_key_generator::InsertKeyGenerator;
+pub use key_chooser::KeyChooser;
+pub use operation::{Operation, OperationChooser};
+pub use operation_proportions::{OperationCounts, OperationProportions};
+pub use partial_workload::PartialWorkload;
+pub use runner::{
+    run_workload, run_workload_sync, Database, DatabaseCapabilities, RunReport, SyncDatabase,
+    Values,
+};
+pub use standard_workload::StandardWorkload;
+
+#[derive(Error, Debug)]
+pub enum WorkloadError {
+    #[error("failed to read workload: {0}")]
+    Io(#[from] io::Error),
+    #[cfg(feature = "serde")]
+    #[error("failed to parse workload: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("failed to write workload: {0}")]
+    Write(#[source] io::Error),
+    #[cfg(feature = "serde")]
+    #[error("failed to serialize workload as TOML: {0}")]
+    TomlSerialize(#[from] toml::ser::Error),
+    #[error("failed to read workload at {}: {source}", path.display())]
+    ReadFile { path: PathBuf, source: io::Error },
+    #[cfg(feature = "serde")]
+    #[error("failed to parse workload at {}: {source}", path.display())]
+    ParseFile {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    #[cfg(feature = "serde")]
+    #[error("failed to (de)serialize workload as JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[cfg(feature = "serde")]
+    #[error("failed to (de)serialize workload as YAML: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+    #[error("{field} is not a finite number")]
+    NotFinite { field: &'static str },
+    #[error("{0} does not fit in 64 bits")]
+    Overflow(&'static str),
+    #[error("operation proportions sum to {sum}, expected 1.0")]
+    ProportionsOutOfRange { sum: f64 },
+    #[error("{field} is {value}, expected a value within [0.0, 1.0]")]
+    InvalidProportion { field: &'static str, value: f64 },
+    #[error("{field} is {value}, expected a fraction within [0.0, 1.0]")]
+    FractionOutOfRange { field: &'static str, value: f64 },
+    #[error("{field} is {value}, expected a zipfian constant within (0.0, 1.0)")]
+    ZipfianConstantOutOfRange { field: &'static str, value: f64 },
+    #[error("{field} contains {value}, expected percentiles within (0.0, 100.0]")]
+    PercentileOutOfRange { field: &'static str, value: f64 },
+    #[error("scale factor is {0}, expected a positive finite number")]
+    InvalidScaleFactor(f64),
+    #[error("recordcount is 0, but the proportion of {operation}, which needs existing records, is {proportion}")]
+    NoRecords {
+        operation: Operation,
+        proportion: f64,
+    },
+    #[error("scanproportion is {scan_proportion} but maxscanlength is 0, so no scan can happen")]
+    InvalidScanConfig { scan_proportion: f64 },
+    #[error("{specs} [[field]] tables are given for {field_count} fields")]
+    FieldCountMismatch { field_count: u64, specs: usize },
+    #[error("{field} is 0, but {distribution} field lengths start at 1")]
+    ZeroFieldLength {
+        field: &'static str,
+        distribution: Distribution,
+    },
+    #[error("{distribution} is not a supported {field}")]
+    UnsupportedDistribution {
+        field: &'static str,
+        distribution: Distribution,
+    },
+    #[error("shard {shard_index} of {shard_count} does not exist")]
+    ShardOutOfRange { shard_index: u64, shard_count: u64 },
+    #[error("{shard_count} shards for {inserts} inserted records, expected at most one shard per record")]
+    TooManyShards { shard_count: u64, inserts: u64 },
+    #[error("{shard_count} shards for {operations} operations, expected at most one shard per operation")]
+    TooFewOperations { shard_count: u64, operations: u64 },
+    #[error("the database does not support {operation}, but its proportion is {proportion}")]
+    UnsupportedOperation {
+        operation: Operation,
+        proportion: f64,
+    },
+    #[error("invalid value `{value}` for {key}: {reason}")]
+    InvalidValue {
+        key: String,
+        value: String,
+        reason: String,
+    },
+    #[error(
+        "line {toml_line} looks like TOML but line {properties_line} looks like Java properties"
+    )]
+    AmbiguousFormat {
+        toml_line: usize,
+        properties_line: usize,
+    },
+    #[error("malformed trace line {line}: `{content}`")]
+    MalformedTrace { line: usize, content: String },
+    #[error("the trace has no operations")]
+    EmptyTrace,
+    #[error("malformed property line `{0}`")]
+    MalformedProperty(String),
+    #[error("unknown properties: {}", .0.join(", "))]
+    UnknownProperties(Vec<String>),
+    #[error("{0} is required to be set")]
+    MissingField(&'static str),
+    #[error(transparent)]
+    Builder(#[from] WorkloadBuilderError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("unknown {kind} `{value}`")]
+pub struct ParseVariantError {
+    kind: &'static str,
+    value: String,
+}
+
+/// Tolerance used when checking that the operation proportions sum to 1.0
+pub(crate) const PROPORTION_EPSILON: f64 = 1e-6;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum Distribution {
     Constant,
     Uniform,
     Zipfian,
     Latest,
+    Exponential,
+    ScrambledZipfian,
+    Sequential,
+    Hotspot,
+}
+
+impl Distribution {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Distribution::Constant => "constant",
+            Distribution::Uniform => "uniform",
+            Distribution::Zipfian => "zipfian",
+            Distribution::Latest => "latest",
+            Distribution::Exponential => "exponential",
+            Distribution::ScrambledZipfian => "scrambledzipfian",
+            Distribution::Sequential => "sequential",
+            Distribution::Hotspot => "hotspot",
+        }
+    }
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Distribution {
+    type Err = ParseVariantError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "constant" => Ok(Distribution::Constant),
+            "uniform" => Ok(Distribution::Uniform),
+            "zipfian" => Ok(Distribution::Zipfian),
+            "latest" => Ok(Distribution::Latest),
+            "exponential" => Ok(Distribution::Exponential),
+            "scrambledzipfian" => Ok(Distribution::ScrambledZipfian),
+            "sequential" => Ok(Distribution::Sequential),
+            "hotspot" => Ok(Distribution::Hotspot),
+            _ => Err(ParseVariantError {
+                kind: "distribution",
+                value: s.to_owned(),
+            }),
+        }
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-#[serde(rename_all = "lowercase")]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum InsertOrder {
     Hashed,
     Ordered,
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-#[serde(rename_all = "lowercase")]
+impl InsertOrder {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InsertOrder::Hashed => "hashed",
+            InsertOrder::Ordered => "ordered",
+        }
+    }
+
+    /// The key of record number `keynum`: the number itself if ordered,
+    /// otherwise its [`fnv_hash64`] to scatter consecutive records
+    pub fn key(&self, keynum: u64) -> u64 {
+        match self {
+            InsertOrder::Hashed => fnv_hash64(keynum),
+            InsertOrder::Ordered => keynum,
+        }
+    }
+}
+
+impl fmt::Display for InsertOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for InsertOrder {
+    type Err = ParseVariantError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "hashed" => Ok(InsertOrder::Hashed),
+            "ordered" => Ok(InsertOrder::Ordered),
+            _ => Err(ParseVariantError {
+                kind: "insert order",
+                value: s.to_owned(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum MeasurementType {
     Histogram,
     Timeseries,
     Raw,
+    HdrHistogram,
+}
+
+impl MeasurementType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MeasurementType::Histogram => "histogram",
+            MeasurementType::Timeseries => "timeseries",
+            MeasurementType::Raw => "raw",
+            MeasurementType::HdrHistogram => "hdrhistogram",
+        }
+    }
+}
+
+impl fmt::Display for MeasurementType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MeasurementType {
+    type Err = ParseVariantError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "histogram" => Ok(MeasurementType::Histogram),
+            "timeseries" => Ok(MeasurementType::Timeseries),
+            "raw" => Ok(MeasurementType::Raw),
+            "hdrhistogram" => Ok(MeasurementType::HdrHistogram),
+            _ => Err(ParseVariantError {
+                kind: "measurement type",
+                value: s.to_owned(),
+            }),
+        }
+    }
+}
+
+/// The phase of a benchmark a driver performs
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Phase {
+    /// Insert the records into the table
+    Load,
+    /// Execute the transactions against the loaded table
+    Run,
 }
 
-#[serde_as]
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+impl Phase {
+    /// The phase selected by YCSB's `dotransactions` property
+    pub fn from_dotransactions(dotransactions: bool) -> Self {
+        if dotransactions {
+            Phase::Run
+        } else {
+            Phase::Load
+        }
+    }
+
+    pub fn dotransactions(&self) -> bool {
+        *self == Phase::Run
+    }
+}
+
+/// When the run phase ends, see [`Workload::run_stop_condition`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum StopCondition {
+    /// After this many operations
+    OperationCount(u64),
+    /// Once this much time has elapsed
+    TimeLimit(Duration),
+    /// After `ops` operations or once `time` has elapsed, whichever
+    /// comes first
+    Either { ops: u64, time: Duration },
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct HistogramConfig {
     /// The range of latencies to track in the histogram (milliseconds)
-    #[serde_as(as = "DurationMilliSeconds")]
+    #[cfg_attr(feature = "serde", serde(with = "As::<DurationMilliSeconds>"))]
     buckets: Duration,
+    /// The latency percentiles to report, either an array or a
+    /// comma-separated string (empty means the 95th and 99th)
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            deserialize_with = "deserialize_percentiles",
+            skip_serializing_if = "Vec::is_empty"
+        )
+    )]
+    percentiles: Vec<f64>,
+}
+
+impl HistogramConfig {
+    #[inline]
+    pub fn buckets(&self) -> Duration {
+        self.buckets
+    }
+    #[inline]
+    pub fn percentiles(&self) -> &[f64] {
+        &self.percentiles
+    }
+}
+
+/// Accept percentiles as either a list of numbers or a comma-separated
+/// string like YCSB's `hdrhistogram.percentiles`
+#[cfg(feature = "serde")]
+fn deserialize_percentiles<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<f64>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Percentiles {
+        List(Vec<f64>),
+        String(String),
+    }
+    match Percentiles::deserialize(deserializer)? {
+        Percentiles::List(list) => Ok(list),
+        Percentiles::String(s) if s.trim().is_empty() => Ok(vec![]),
+        Percentiles::String(s) => s
+            .split(',')
+            .map(|p| p.trim().parse().map_err(serde::de::Error::custom))
+            .collect(),
+    }
 }
 
-#[serde_as]
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct TimeseriesConfig {
     /// Granularity for time series (in milliseconds)
-    #[serde_as(as = "DurationMilliSeconds")]
+    #[cfg_attr(feature = "serde", serde(with = "As::<DurationMilliSeconds>"))]
     granularity: Duration,
 }
 
-#[serde_as]
-#[derive(Serialize, Deserialize, Debug, PartialEq, PartialOrd, Clone, Builder)]
-#[builder(pattern = "owned", default)]
-#[serde(default)]
+impl TimeseriesConfig {
+    #[inline]
+    pub fn granularity(&self) -> Duration {
+        self.granularity
+    }
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HdrHistogramConfig {
+    /// The latency percentiles to report
+    percentiles: Vec<f64>,
+    /// The file the histogram is written to
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "outputfile", skip_serializing_if = "Option::is_none")
+    )]
+    output_file: Option<String>,
+}
+
+impl HdrHistogramConfig {
+    #[inline]
+    pub fn percentiles(&self) -> &[f64] {
+        &self.percentiles
+    }
+    #[inline]
+    pub fn output_file(&self) -> Option<&str> {
+        self.output_file.as_deref()
+    }
+}
+
+impl Default for HdrHistogramConfig {
+    fn default() -> Self {
+        Self {
+            percentiles: vec![50., 95., 99., 99.9],
+            output_file: None,
+        }
+    }
+}
+
+/// Service level objectives: the p99 latency each kind of operation
+/// should stay under (milliseconds)
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SloConfig {
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            with = "As::<Option<DurationMilliSeconds>>",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    read: Option<Duration>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            with = "As::<Option<DurationMilliSeconds>>",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    update: Option<Duration>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            with = "As::<Option<DurationMilliSeconds>>",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    insert: Option<Duration>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            with = "As::<Option<DurationMilliSeconds>>",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    scan: Option<Duration>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "readmodifywrite",
+            with = "As::<Option<DurationMilliSeconds>>",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    read_modify_write: Option<Duration>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            with = "As::<Option<DurationMilliSeconds>>",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    delete: Option<Duration>,
+}
+
+impl SloConfig {
+    fn target_mut(&mut self, operation: Operation) -> &mut Option<Duration> {
+        match operation {
+            Operation::Read => &mut self.read,
+            Operation::Update => &mut self.update,
+            Operation::Insert => &mut self.insert,
+            Operation::Scan => &mut self.scan,
+            Operation::ReadModifyWrite => &mut self.read_modify_write,
+            Operation::Delete => &mut self.delete,
+        }
+    }
+    /// Require the p99 latency of `operation` to stay under `latency`
+    pub fn with_p99(mut self, operation: Operation, latency: Duration) -> Self {
+        *self.target_mut(operation) = Some(latency);
+        self
+    }
+    /// The p99 latency `operation` should stay under, if any
+    pub fn p99(&self, operation: Operation) -> Option<Duration> {
+        match operation {
+            Operation::Read => self.read,
+            Operation::Update => self.update,
+            Operation::Insert => self.insert,
+            Operation::Scan => self.scan,
+            Operation::ReadModifyWrite => self.read_modify_write,
+            Operation::Delete => self.delete,
+        }
+    }
+    /// Whether no objective is set
+    pub fn is_empty(&self) -> bool {
+        *self == SloConfig::default()
+    }
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExponentialConfig {
+    /// Percentage of operations that access the hot set
+    /// described by `frac`
+    percentile: f64,
+    /// Fraction of the dataset accessed `percentile` percent of the time
+    frac: f64,
+}
+
+impl ExponentialConfig {
+    #[inline]
+    pub fn percentile(&self) -> f64 {
+        self.percentile
+    }
+    #[inline]
+    pub fn frac(&self) -> f64 {
+        self.frac
+    }
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ZipfianConfig {
+    /// Skew of the zipfian request distribution
+    constant: f64,
+}
+
+impl ZipfianConfig {
+    #[inline]
+    pub fn constant(&self) -> f64 {
+        self.constant
+    }
+}
+
+/// The length of one field of a record, overriding `fieldlength` and
+/// `fieldlengthdistribution`
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FieldSpec {
+    /// The size of the field
+    length: u64,
+    /// The distribution used to choose the length of the field
+    /// (could be: constant, uniform, zipfian)
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "lengthdistribution",
+            default = "FieldSpec::default_distribution"
+        )
+    )]
+    length_distribution: Distribution,
+}
+
+impl FieldSpec {
+    pub fn new(length: u64, length_distribution: Distribution) -> Self {
+        Self {
+            length,
+            length_distribution,
+        }
+    }
+    #[cfg(feature = "serde")]
+    fn default_distribution() -> Distribution {
+        Distribution::Constant
+    }
+    #[inline]
+    pub fn length(&self) -> u64 {
+        self.length
+    }
+    #[inline]
+    pub fn length_distribution(&self) -> Distribution {
+        self.length_distribution
+    }
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScanConfig {
+    /// Skew of the scan lengths when scanlengthdistribution is zipfian,
+    /// independent of the request distribution's skew
+    #[cfg_attr(feature = "serde", serde(rename = "zipfianconstant"))]
+    zipfian_constant: f64,
+}
+
+impl ScanConfig {
+    #[inline]
+    pub fn zipfian_constant(&self) -> f64 {
+        self.zipfian_constant
+    }
+}
+
+impl Default for HistogramConfig {
+    fn default() -> Self {
+        Self {
+            buckets: Duration::from_millis(1000),
+            percentiles: vec![],
+        }
+    }
+}
+
+impl Default for TimeseriesConfig {
+    fn default() -> Self {
+        Self {
+            granularity: Duration::from_millis(1000),
+        }
+    }
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        Self {
+            zipfian_constant: generator::ZIPFIAN_CONSTANT,
+        }
+    }
+}
+
+impl Default for ZipfianConfig {
+    fn default() -> Self {
+        Self {
+            constant: generator::ZIPFIAN_CONSTANT,
+        }
+    }
+}
+
+impl Default for ExponentialConfig {
+    fn default() -> Self {
+        Self {
+            percentile: 95.,
+            frac: 0.8571428571,
+        }
+    }
+}
+
+/// What a random number generator drawn by [`Workload::salted_rng`] is
+/// used for
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum RngSalt {
+    /// Choosing the operation of each request
+    Operations,
+    /// Choosing the fields and scan length of each request
+    Requests,
+    /// Choosing the lengths of field `index`
+    FieldLength(u64),
+}
+
+impl RngSalt {
+    fn value(self) -> u64 {
+        match self {
+            RngSalt::Operations => 1,
+            RngSalt::Requests => 2,
+            RngSalt::FieldLength(index) => (3 << 32) | index,
+        }
+    }
+}
+
+/// Every distribution of a workload, see [`Workload::distributions`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Distributions {
+    /// The distribution of the keys operated on by transactions
+    pub request: Distribution,
+    /// The distribution of field lengths, unless overridden by a
+    /// `[[field]]` table
+    pub field_length: Distribution,
+    /// The distribution of the number of records scanned
+    pub scan_length: Distribution,
+}
+
+/// The settings of a YCSB workload
+///
+/// Floating-point fields are compared as `f64`, so a workload with a NaN
+/// field is neither equal to nor ordered against any workload, itself
+/// included. [`Workload::validate`] rejects such workloads.
+#[derive(Debug, PartialEq, PartialOrd, Clone, Builder)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[builder(pattern = "owned", default, derive(Clone))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Workload {
     /// The name of the workload class to use
     workload: String,
@@ -55,88 +707,163 @@ pub struct Workload {
     /// the load phase or the number of records already in the
     /// table before the run phase
     /// (required to be set)
-    #[serde(rename = "recordcount")]
+    #[cfg_attr(feature = "serde", serde(rename = "recordcount"))]
     record_count: u64,
     /// The number of operations to use during the run phase.
     /// (required to be set)
-    #[serde(rename = "operationcount")]
+    #[cfg_attr(feature = "serde", serde(rename = "operationcount"))]
     operation_count: u64,
     /// The number of thread
-    #[serde(rename = "threadcount")]
+    /// (0 means one per available CPU)
+    #[cfg_attr(feature = "serde", serde(rename = "threadcount"))]
     thread_count: u64,
+    /// The target number of operations per second across all threads
+    /// (0 means unlimited)
+    target: u64,
     /// The number of insertions to do, if different from recordcount.
     /// Used with insertstart to grow an existing table
-    #[serde(rename = "insertcount")]
+    /// (0 means recordcount, see [`Workload::effective_insert_count`])
+    #[cfg_attr(feature = "serde", serde(rename = "insertcount"))]
     insert_count: u64,
     /// The offset of the first insertion
-    #[serde(rename = "insertstart")]
+    #[cfg_attr(feature = "serde", serde(rename = "insertstart"))]
     insert_start: u64,
     /// The number of fields in a record
-    #[serde(rename = "fieldcount")]
+    #[cfg_attr(feature = "serde", serde(rename = "fieldcount"))]
     field_count: u64,
+    /// The prefix of field names, suffixed by the field index
+    #[cfg_attr(feature = "serde", serde(rename = "fieldnameprefix"))]
+    field_name_prefix: String,
     /// The size of each field (in bytes)
-    #[serde(rename = "fieldlength")]
+    #[cfg_attr(feature = "serde", serde(rename = "fieldlength"))]
     field_length: u64,
     /// Should read all field
-    #[serde(rename = "readallfields")]
+    #[cfg_attr(feature = "serde", serde(rename = "readallfields"))]
     read_all_fields: bool,
     /// Should write all fields on update
-    #[serde(rename = "writeallfields")]
+    #[cfg_attr(feature = "serde", serde(rename = "writeallfields"))]
     write_all_fields: bool,
-    /// The distribution usd to choose the lenght of a field
-    /// (could be: constant, uniform, zipfian, ~~latest~~)
-    #[serde(rename = "fieldlengthdistribution")]
+    /// Should write deterministic field values so reads can be verified
+    /// (requires a constant field length distribution)
+    #[cfg_attr(feature = "serde", serde(rename = "dataintegrity"))]
+    data_integrity: bool,
+    /// The distribution used to choose the length of a field
+    /// (could be: constant, uniform, zipfian)
+    #[cfg_attr(feature = "serde", serde(rename = "fieldlengthdistribution"))]
     field_length_distribution: Distribution,
     /// What proportion of operations are reads
-    #[serde(rename = "readproportion")]
+    #[cfg_attr(feature = "serde", serde(rename = "readproportion"))]
     read_proportion: f64,
     /// What proportion of operations are updates
-    #[serde(rename = "updateproportion")]
+    #[cfg_attr(feature = "serde", serde(rename = "updateproportion"))]
     update_proportion: f64,
     /// What proportion of operations are inserts
-    #[serde(rename = "insertproportion")]
+    #[cfg_attr(feature = "serde", serde(rename = "insertproportion"))]
     insert_proportion: f64,
     /// What proportion of operations read then modify a record
-    #[serde(rename = "readmodifywriteproportion")]
+    #[cfg_attr(feature = "serde", serde(rename = "readmodifywriteproportion"))]
     read_modify_write_proportion: f64,
     /// What proportion of operations are scans
-    #[serde(rename = "scanproportion")]
+    #[cfg_attr(feature = "serde", serde(rename = "scanproportion"))]
     scan_proportion: f64,
+    /// What proportion of operations are deletes
+    #[cfg_attr(feature = "serde", serde(rename = "deleteproportion"))]
+    delete_proportion: f64,
     /// On a single scan, the maximum number of records to access
-    #[serde(rename = "maxscanlength")]
+    #[cfg_attr(feature = "serde", serde(rename = "maxscanlength"))]
     max_scan_length: u64,
     /// The distribution used to choose the number of records to access on a scan
     /// (could be: ~~constant~~, uniform, zipfian, ~~latest~~)
-    #[serde(rename = "scanlengthdistribution")]
+    #[cfg_attr(feature = "serde", serde(rename = "scanlengthdistribution"))]
     scan_length_distribution: Distribution,
     /// Should records be inserted in order or pseudo-randomly
-    #[serde(rename = "insertorder")]
+    #[cfg_attr(feature = "serde", serde(rename = "insertorder"))]
     insert_order: InsertOrder,
+    /// The minimum number of digits of the numeric part of a key,
+    /// left padded with zeros
+    #[cfg_attr(feature = "serde", serde(rename = "zeropadding"))]
+    zero_padding: u64,
     /// The distribution of requests across the keyspace
-    /// (could be: ~~constant~~, uniform, zipfian, latest)
-    #[serde(rename = "requestdistribution")]
+    /// (could be: ~~constant~~, uniform, zipfian, latest, exponential,
+    /// scrambledzipfian, sequential, hotspot)
+    #[cfg_attr(feature = "serde", serde(rename = "requestdistribution"))]
     request_distribution: Distribution,
     /// Percentage of data items that constitute the hot set
-    #[serde(rename = "readcount")]
+    #[cfg_attr(feature = "serde", serde(rename = "hotspotdatafraction"))]
     hotspot_data_fraction: f64,
     /// Percentage of operations that access the hot set
-    #[serde(rename = "hotspotopnfraction")]
+    #[cfg_attr(feature = "serde", serde(rename = "hotspotopnfraction"))]
     hotspot_operation_fraction: f64,
     /// Maximum execution time in seconds
-    #[serde_as(as = "DurationSeconds<u64>")]
-    #[serde(rename = "maxexecutiontime")]
+    /// (0 means unlimited)
+    #[cfg_attr(feature = "serde", serde(with = "As::<DurationSeconds<u64>>"))]
+    #[cfg_attr(feature = "serde", serde(rename = "maxexecutiontime"))]
     max_execution_time: Duration,
+    /// The number of times a failed insertion is retried
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "core_workload_insertion_retry_limit")
+    )]
+    insertion_retry_limit: u64,
+    /// The interval between insertion retries in seconds
+    #[cfg_attr(feature = "serde", serde(with = "As::<DurationSeconds<u64>>"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "core_workload_insertion_retry_interval")
+    )]
+    insertion_retry_interval: Duration,
+    /// The interval between status reports in seconds, also accepted as
+    /// YCSB's `status.interval`
+    #[cfg_attr(feature = "serde", serde(with = "As::<DurationSeconds<u64>>"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "statusinterval", alias = "status.interval")
+    )]
+    status_interval: Duration,
     /// The name of the database table to run queries against
     table: String,
     /// The column family of fields (required by some databases)
-    #[serde(rename = "columnfamily")]
+    #[cfg_attr(feature = "serde", serde(rename = "columnfamily"))]
     column_family: String,
     /// How the latency measurements are presented
-    ///(could be: histogram, timeseries, raw)
-    #[serde(rename = "measurementtype")]
+    ///(could be: histogram, timeseries, raw, hdrhistogram)
+    #[cfg_attr(feature = "serde", serde(rename = "measurementtype"))]
     measurement_type: MeasurementType,
+    /// The file measurements are exported to
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "exportfile", skip_serializing_if = "Option::is_none")
+    )]
+    #[builder(setter(strip_option))]
+    export_file: Option<String>,
+    /// The interval between measurement exports in seconds
+    /// (0 exports only at the end of the run)
+    #[cfg_attr(feature = "serde", serde(with = "As::<DurationSeconds<u64>>"))]
+    #[cfg_attr(feature = "serde", serde(rename = "exportmeasurementsinterval"))]
+    export_measurements_interval: Duration,
+    /// Seed of the random number generators choosing operations and keys,
+    /// two runs with the same seed issue identical requests
+    /// (`None` seeds from system entropy)
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    #[builder(setter(strip_option))]
+    seed: Option<u64>,
     histogram: HistogramConfig,
     timeseries: TimeseriesConfig,
+    exponential: ExponentialConfig,
+    zipfian: ZipfianConfig,
+    scan: ScanConfig,
+    hdrhistogram: HdrHistogramConfig,
+    /// The p99 latency objectives checked by [`RunReport::slo_violations`]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "SloConfig::is_empty"))]
+    slo: SloConfig,
+    /// The length of each field, one `[[field]]` table per field
+    /// (empty means every field follows `fieldlength` and
+    /// `fieldlengthdistribution`)
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "field", skip_serializing_if = "Vec::is_empty")
+    )]
+    field_specs: Vec<FieldSpec>,
 }
 
 impl Default for Workload {
@@ -146,167 +873,1080 @@ impl Default for Workload {
             record_count: 1000000,
             operation_count: 3000000,
             thread_count: 500,
+            target: 0,
             insert_count: 0,
             insert_start: 0,
             field_count: 10,
+            field_name_prefix: "field".to_owned(),
             field_length: 100,
             read_all_fields: true,
             write_all_fields: false,
+            data_integrity: false,
             field_length_distribution: Distribution::Constant,
             read_proportion: 0.95,
             update_proportion: 0.05,
             insert_proportion: 0.,
             read_modify_write_proportion: 0.,
             scan_proportion: 0.,
+            delete_proportion: 0.,
             max_scan_length: 1000,
             scan_length_distribution: Distribution::Uniform,
             insert_order: InsertOrder::Hashed,
+            zero_padding: 1,
             request_distribution: Distribution::Zipfian,
             hotspot_data_fraction: 0.2,
             hotspot_operation_fraction: 0.8,
             max_execution_time: Duration::from_secs(0),
+            insertion_retry_limit: 0,
+            insertion_retry_interval: Duration::from_secs(3),
+            status_interval: Duration::from_secs(10),
             table: "usertable".to_owned(),
             column_family: "".to_owned(),
             measurement_type: MeasurementType::Histogram,
-            histogram: HistogramConfig {
-                buckets: Duration::from_millis(1000),
-            },
-            timeseries: TimeseriesConfig {
-                granularity: Duration::from_millis(1000),
-            },
+            export_file: None,
+            export_measurements_interval: Duration::from_secs(0),
+            seed: None,
+            histogram: HistogramConfig::default(),
+            timeseries: TimeseriesConfig::default(),
+            exponential: ExponentialConfig::default(),
+            zipfian: ZipfianConfig::default(),
+            scan: ScanConfig::default(),
+            hdrhistogram: HdrHistogramConfig::default(),
+            slo: SloConfig::default(),
+            field_specs: vec![],
         }
     }
 }
 
-impl Workload {
-    pub fn from_toml_str(toml: &str) -> Self {
-        toml::from_str(toml).unwrap()
+/// Format `n` with an SI suffix and at most one decimal, e.g. `2.5M`
+fn si_count(n: u64) -> String {
+    for (unit, suffix) in [(1e12, "T"), (1e9, "G"), (1e6, "M"), (1e3, "K")] {
+        if n as f64 >= unit {
+            let scaled = format!("{:.1}", n as f64 / unit);
+            return format!("{}{}", scaled.trim_end_matches(".0"), suffix);
+        }
     }
-    pub fn from_toml_file<P: AsRef<Path>>(path: P) -> Self {
-        Workload::from_toml_str(&fs::read_to_string(path).unwrap())
+    n.to_string()
+}
+
+impl fmt::Display for Workload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "workload={} records={} ops={} threads={} R/U/I/S/RMW/D={}/{}/{}/{}/{}/{} dist={}",
+            self.workload,
+            si_count(self.record_count),
+            si_count(self.operation_count),
+            self.thread_count,
+            self.read_proportion,
+            self.update_proportion,
+            self.insert_proportion,
+            self.scan_proportion,
+            self.read_modify_write_proportion,
+            self.delete_proportion,
+            self.request_distribution,
+        )
     }
-    pub fn a(record_count: u64, operation_count: u64) -> Self {
-        WorkloadBuilder::default()
-            .record_count(record_count)
-            .operation_count(operation_count)
-            .read_all_fields(true)
-            .insert_proportion(0.)
-            .read_proportion(0.5)
-            .scan_proportion(0.)
-            .update_proportion(0.5)
-            .request_distribution(Distribution::Uniform)
-            .build()
-            .unwrap()
+}
+
+impl WorkloadBuilder {
+    /// Build the workload, failing if a required field was never set
+    /// or if the result does not pass [`Workload::validate`]
+    pub fn build_validated(self) -> Result<Workload, WorkloadError> {
+        if self.record_count.is_none() {
+            return Err(WorkloadError::MissingField("recordcount"));
+        }
+        if self.operation_count.is_none() {
+            return Err(WorkloadError::MissingField("operationcount"));
+        }
+        let workload = self.build()?;
+        workload.validate()?;
+        Ok(workload)
     }
-    pub fn b(record_count: u64, operation_count: u64) -> Self {
-        WorkloadBuilder::default()
-            .record_count(record_count)
-            .operation_count(operation_count)
-            .read_all_fields(true)
-            .insert_proportion(0.)
-            .read_proportion(0.95)
-            .scan_proportion(0.)
-            .update_proportion(0.05)
-            .request_distribution(Distribution::Uniform)
-            .build()
-            .unwrap()
+
+    /// Set the skew of the zipfian request distribution
+    pub fn zipfian_constant(self, constant: f64) -> Self {
+        self.zipfian(ZipfianConfig { constant })
     }
-    pub fn c(record_count: u64, operation_count: u64) -> Self {
-        WorkloadBuilder::default()
-            .record_count(record_count)
-            .operation_count(operation_count)
-            .read_all_fields(true)
-            .insert_proportion(0.)
-            .read_proportion(1.)
-            .scan_proportion(0.)
-            .update_proportion(0.)
-            .request_distribution(Distribution::Uniform)
-            .build()
-            .unwrap()
+
+    /// Set the range of latencies tracked by the histogram, keeping any
+    /// other histogram setting
+    pub fn histogram_buckets(mut self, buckets: Duration) -> Self {
+        let mut histogram = self.histogram.take().unwrap_or_default();
+        histogram.buckets = buckets;
+        self.histogram(histogram)
     }
-    pub fn d(record_count: u64, operation_count: u64) -> Self {
-        WorkloadBuilder::default()
-            .record_count(record_count)
-            .operation_count(operation_count)
-            .read_all_fields(true)
-            .insert_proportion(0.05)
-            .read_proportion(0.95)
-            .scan_proportion(0.)
-            .update_proportion(0.)
-            .request_distribution(Distribution::Latest)
-            .build()
-            .unwrap()
+
+    /// Set the granularity of the time series, which must be positive to
+    /// pass [`Workload::validate`]
+    pub fn timeseries_granularity(self, granularity: Duration) -> Self {
+        self.timeseries(TimeseriesConfig { granularity })
     }
-    pub fn e(record_count: u64, operation_count: u64) -> Self {
-        WorkloadBuilder::default()
-            .record_count(record_count)
-            .operation_count(operation_count)
-            .read_all_fields(true)
-            .insert_proportion(0.05)
-            .read_proportion(0.)
-            .scan_proportion(0.95)
-            .update_proportion(0.)
-            .request_distribution(Distribution::Uniform)
-            .max_scan_length(1)
-            .scan_length_distribution(Distribution::Uniform)
-            .build()
-            .unwrap()
+
+    /// A copy of the builder, to branch several workloads off common
+    /// settings
+    pub fn fork(&self) -> Self {
+        self.clone()
     }
-    pub fn f(record_count: u64, operation_count: u64) -> Self {
-        WorkloadBuilder::default()
-            .record_count(record_count)
-            .operation_count(operation_count)
-            .read_all_fields(true)
-            .insert_proportion(0.)
-            .read_modify_write_proportion(0.5)
-            .read_proportion(0.5)
-            .scan_proportion(0.)
-            .update_proportion(0.)
-            .request_distribution(Distribution::Uniform)
-            .build()
-            .unwrap()
+
+    /// Set every operation proportion at once
+    pub fn proportions(self, proportions: OperationProportions) -> Self {
+        self.read_proportion(proportions.read)
+            .update_proportion(proportions.update)
+            .insert_proportion(proportions.insert)
+            .scan_proportion(proportions.scan)
+            .read_modify_write_proportion(proportions.read_modify_write)
+            .delete_proportion(proportions.delete)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use std::fs;
-
-    const DEFAULT_CONFIG_STRING: &str = r#"
-workload = "core"
-recordcount = 1000000
-operationcount = 3000000
-threadcount = 500
-insertcount = 0
-insertstart = 0
-fieldcount = 10
-fieldlength = 100
-readallfields = true
-writeallfields = false
-fieldlengthdistribution = "constant"
+impl Workload {
+    #[inline]
+    pub fn workload(&self) -> &str {
+        &self.workload
+    }
+    #[inline]
+    pub fn record_count(&self) -> u64 {
+        self.record_count
+    }
+    #[inline]
+    pub fn operation_count(&self) -> u64 {
+        self.operation_count
+    }
+    #[inline]
+    pub fn thread_count(&self) -> u64 {
+        self.thread_count
+    }
+    #[inline]
+    pub fn target(&self) -> u64 {
+        self.target
+    }
+    #[inline]
+    pub fn insert_count(&self) -> u64 {
+        self.insert_count
+    }
+    #[inline]
+    pub fn insert_start(&self) -> u64 {
+        self.insert_start
+    }
+    #[inline]
+    pub fn field_count(&self) -> u64 {
+        self.field_count
+    }
+    #[inline]
+    pub fn field_name_prefix(&self) -> &str {
+        &self.field_name_prefix
+    }
+    #[inline]
+    pub fn field_length(&self) -> u64 {
+        self.field_length
+    }
+    #[inline]
+    pub fn read_all_fields(&self) -> bool {
+        self.read_all_fields
+    }
+    #[inline]
+    pub fn write_all_fields(&self) -> bool {
+        self.write_all_fields
+    }
+    #[inline]
+    pub fn data_integrity(&self) -> bool {
+        self.data_integrity
+    }
+    #[inline]
+    pub fn field_length_distribution(&self) -> Distribution {
+        self.field_length_distribution
+    }
+    #[inline]
+    pub fn read_proportion(&self) -> f64 {
+        self.read_proportion
+    }
+    #[inline]
+    pub fn update_proportion(&self) -> f64 {
+        self.update_proportion
+    }
+    #[inline]
+    pub fn insert_proportion(&self) -> f64 {
+        self.insert_proportion
+    }
+    #[inline]
+    pub fn read_modify_write_proportion(&self) -> f64 {
+        self.read_modify_write_proportion
+    }
+    #[inline]
+    pub fn scan_proportion(&self) -> f64 {
+        self.scan_proportion
+    }
+    #[inline]
+    pub fn delete_proportion(&self) -> f64 {
+        self.delete_proportion
+    }
+    #[inline]
+    pub fn max_scan_length(&self) -> u64 {
+        self.max_scan_length
+    }
+    #[inline]
+    pub fn scan_length_distribution(&self) -> Distribution {
+        self.scan_length_distribution
+    }
+    #[inline]
+    pub fn insert_order(&self) -> InsertOrder {
+        self.insert_order
+    }
+    #[inline]
+    pub fn zero_padding(&self) -> u64 {
+        self.zero_padding
+    }
+    #[inline]
+    pub fn request_distribution(&self) -> Distribution {
+        self.request_distribution
+    }
+    pub fn distributions(&self) -> Distributions {
+        Distributions {
+            request: self.request_distribution,
+            field_length: self.field_length_distribution,
+            scan_length: self.scan_length_distribution,
+        }
+    }
+    #[inline]
+    pub fn hotspot_data_fraction(&self) -> f64 {
+        self.hotspot_data_fraction
+    }
+    #[inline]
+    pub fn hotspot_operation_fraction(&self) -> f64 {
+        self.hotspot_operation_fraction
+    }
+    /// The keys of the hot set of the hotspot distribution, which receive
+    /// `hotspot_operation_fraction` of the requests
+    pub fn hot_set_range(&self) -> std::ops::Range<u64> {
+        0..(self.record_count as f64 * self.hotspot_data_fraction) as u64
+    }
+    /// The time limit of the run phase, `None` if it only stops after
+    /// `operation_count` operations
+    #[inline]
+    pub fn max_execution_time(&self) -> Option<Duration> {
+        (!self.max_execution_time.is_zero()).then_some(self.max_execution_time)
+    }
+    /// When the run phase ends: after `operation_count` operations, once
+    /// `max_execution_time` has elapsed, or whichever comes first if both
+    /// are set. As in YCSB, an `operation_count` of 0 with a time limit
+    /// runs until the time limit.
+    pub fn run_stop_condition(&self) -> StopCondition {
+        match (self.operation_count, self.max_execution_time()) {
+            (ops, None) => StopCondition::OperationCount(ops),
+            (0, Some(time)) => StopCondition::TimeLimit(time),
+            (ops, Some(time)) => StopCondition::Either { ops, time },
+        }
+    }
+    #[inline]
+    pub fn insertion_retry_limit(&self) -> u64 {
+        self.insertion_retry_limit
+    }
+    #[inline]
+    pub fn insertion_retry_interval(&self) -> Duration {
+        self.insertion_retry_interval
+    }
+    #[inline]
+    pub fn status_interval(&self) -> Duration {
+        self.status_interval
+    }
+    #[inline]
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+    #[inline]
+    pub fn column_family(&self) -> &str {
+        &self.column_family
+    }
+    #[inline]
+    pub fn measurement_type(&self) -> MeasurementType {
+        self.measurement_type
+    }
+    #[inline]
+    pub fn export_file(&self) -> Option<&str> {
+        self.export_file.as_deref()
+    }
+    #[inline]
+    pub fn export_measurements_interval(&self) -> Duration {
+        self.export_measurements_interval
+    }
+    #[inline]
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+    #[inline]
+    pub fn histogram(&self) -> &HistogramConfig {
+        &self.histogram
+    }
+    #[inline]
+    pub fn timeseries(&self) -> &TimeseriesConfig {
+        &self.timeseries
+    }
+    #[inline]
+    pub fn exponential(&self) -> &ExponentialConfig {
+        &self.exponential
+    }
+    #[inline]
+    pub fn zipfian(&self) -> &ZipfianConfig {
+        &self.zipfian
+    }
+    #[inline]
+    pub fn scan(&self) -> &ScanConfig {
+        &self.scan
+    }
+    #[inline]
+    pub fn hdrhistogram(&self) -> &HdrHistogramConfig {
+        &self.hdrhistogram
+    }
+    #[inline]
+    pub fn slo(&self) -> &SloConfig {
+        &self.slo
+    }
+    #[inline]
+    pub fn field_specs(&self) -> &[FieldSpec] {
+        &self.field_specs
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Workload {
+    pub fn from_toml_str(toml: &str) -> Result<Self, WorkloadError> {
+        Ok(toml::from_str(toml)?)
+    }
+    pub fn from_toml_str_unwrap(toml: &str) -> Self {
+        Workload::from_toml_str(toml).unwrap()
+    }
+    /// Parse the TOML workload file at `path`, reporting the path in
+    /// any error
+    pub fn from_toml_file<P: AsRef<Path>>(path: P) -> Result<Self, WorkloadError> {
+        let path = path.as_ref();
+        let toml = fs::read_to_string(path).map_err(|source| WorkloadError::ReadFile {
+            path: path.to_owned(),
+            source,
+        })?;
+        toml::from_str(&toml).map_err(|source| WorkloadError::ParseFile {
+            path: path.to_owned(),
+            source,
+        })
+    }
+    /// Parse the copy of the standard workload file embedded in the
+    /// crate, which does not depend on the `workloads` directory existing
+    /// at runtime
+    pub fn standard(standard: StandardWorkload) -> Workload {
+        Workload::from_toml_str(standard.toml())
+            .unwrap_or_else(|e| panic!("embedded {} is invalid: {}", standard, e))
+    }
+    pub fn from_toml_reader<R: io::Read>(mut reader: R) -> Result<Self, WorkloadError> {
+        let mut toml = String::new();
+        reader.read_to_string(&mut toml)?;
+        Workload::from_toml_str(&toml)
+    }
+    /// Write the workload to `writer` as TOML, in the layout of the files
+    /// in `workloads`
+    pub fn write_toml<W: io::Write>(&self, mut writer: W) -> Result<(), WorkloadError> {
+        // toml 0.5 only serializes to a `String`
+        let toml = toml::to_string(self)?;
+        writer
+            .write_all(toml.as_bytes())
+            .map_err(WorkloadError::Write)
+    }
+    pub fn from_json_str(json: &str) -> Result<Self, WorkloadError> {
+        Ok(serde_json::from_str(json)?)
+    }
+    pub fn to_json_string(&self) -> Result<String, WorkloadError> {
+        Ok(serde_json::to_string(self)?)
+    }
+    pub fn from_yaml_str(yaml: &str) -> Result<Self, WorkloadError> {
+        Ok(serde_yaml::from_str(yaml)?)
+    }
+    pub fn to_yaml_string(&self) -> Result<String, WorkloadError> {
+        Ok(serde_yaml::to_string(self)?)
+    }
+    /// A flat JSON object with the settings most worth displaying, e.g. on
+    /// a dashboard, rather than the whole configuration
+    pub fn summary_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "workload": self.workload,
+            "records": self.record_count,
+            "operations": self.operation_count,
+            "threads": self.effective_thread_count(),
+            "target_ops_per_sec": self.target,
+            "read_proportion": self.read_proportion,
+            "update_proportion": self.update_proportion,
+            "insert_proportion": self.insert_proportion,
+            "scan_proportion": self.scan_proportion,
+            "read_modify_write_proportion": self.read_modify_write_proportion,
+            "delete_proportion": self.delete_proportion,
+            "request_distribution": self.request_distribution.as_str(),
+            "field_length_distribution": self.field_length_distribution.as_str(),
+            "scan_length_distribution": self.scan_length_distribution.as_str(),
+            "insert_order": self.insert_order.as_str(),
+            "fields_per_record": self.field_count,
+            "field_length": self.field_length,
+        })
+    }
+}
+
+impl Workload {
+    /// Names of the fields of a record: `field_name_prefix` followed
+    /// by `0..field_count`
+    pub fn field_names(&self) -> Vec<String> {
+        (0..self.field_count)
+            .map(|i| format!("{}{}", self.field_name_prefix, i))
+            .collect()
+    }
+    /// The length of field `index`: its `[[field]]` table if any, otherwise
+    /// `fieldlength` and `fieldlengthdistribution`
+    pub fn field_spec(&self, index: u64) -> FieldSpec {
+        self.field_specs
+            .get(index as usize)
+            .copied()
+            .unwrap_or(FieldSpec {
+                length: self.field_length,
+                length_distribution: self.field_length_distribution,
+            })
+    }
+    /// Every field index if `all` is set, otherwise a random one
+    fn fields_to_access(&self, all: bool, rng: &mut SmallRng) -> Vec<u64> {
+        if all || self.field_count == 0 {
+            return (0..self.field_count).collect();
+        }
+        vec![rng.gen_range(0..self.field_count)]
+    }
+    /// The indices of the fields a read or scan accesses: every field if
+    /// `read_all_fields` is set, otherwise a random one
+    pub fn fields_to_read(&self, rng: &mut SmallRng) -> Vec<u64> {
+        self.fields_to_access(self.read_all_fields, rng)
+    }
+    /// The indices of the fields an update writes: every field if
+    /// `write_all_fields` is set, otherwise a random one
+    pub fn fields_to_write(&self, rng: &mut SmallRng) -> Vec<u64> {
+        self.fields_to_access(self.write_all_fields, rng)
+    }
+    /// The database key of record `key`: `user` followed by the record
+    /// number padded to `zero_padding` digits
+    pub fn format_key(&self, key: u64) -> String {
+        format!("user{:0width$}", key, width = self.zero_padding as usize)
+    }
+    /// The database key of record number `keynum`, hashed first if
+    /// `insert_order` is hashed, as YCSB names the records it inserts
+    pub fn record_key(&self, keynum: u64) -> String {
+        self.format_key(self.insert_order.key(keynum))
+    }
+    /// The value YCSB writes to field `field_index` of record `key` in
+    /// data integrity mode: the key and field name followed by a chain
+    /// of hash codes, truncated to `field_length` bytes
+    pub fn deterministic_field_value(&self, key: u64, field_index: u64) -> Vec<u8> {
+        let length = self.field_spec(field_index).length;
+        self.deterministic_field_value_with_length(key, field_index, length)
+    }
+    /// Like [`Workload::deterministic_field_value`], but truncated to
+    /// `length` bytes, e.g. a length drawn by a [`FieldLengthGenerator`]
+    pub fn deterministic_field_value_with_length(
+        &self,
+        key: u64,
+        field_index: u64,
+        length: u64,
+    ) -> Vec<u8> {
+        // Java's String.hashCode
+        fn hash_code(s: &str) -> i32 {
+            s.bytes()
+                .fold(0i32, |h, b| h.wrapping_mul(31).wrapping_add(b as i32))
+        }
+        let size = length as usize;
+        let mut value = format!(
+            "{}:{}{}",
+            self.format_key(key),
+            self.field_name_prefix,
+            field_index
+        );
+        while value.len() < size {
+            let hash = hash_code(&value);
+            value.push(':');
+            value.push_str(&hash.to_string());
+        }
+        value.truncate(size);
+        value.into_bytes()
+    }
+    /// The number of threads a driver should run: `thread_count`, or the
+    /// number of available CPUs if `thread_count` is 0
+    pub fn effective_thread_count(&self) -> u64 {
+        if self.thread_count > 0 {
+            return self.thread_count;
+        }
+        std::thread::available_parallelism().map_or(1, |n| n.get() as u64)
+    }
+    /// The number of operations per second each thread should issue to
+    /// meet `target`, or `None` if throughput is unlimited
+    pub fn per_thread_target_ops_per_sec(&self) -> Option<f64> {
+        (self.target > 0).then(|| self.target as f64 / self.effective_thread_count() as f64)
+    }
+    /// The number of records the load phase inserts: `insert_count`, or
+    /// `record_count` if `insert_count` is 0
+    pub fn effective_insert_count(&self) -> u64 {
+        if self.insert_count == 0 {
+            self.record_count
+        } else {
+            self.insert_count
+        }
+    }
+    /// The keys inserted by the load phase, [`Workload::effective_insert_count`]
+    /// keys from `insert_start`
+    pub fn insert_key_range(&self) -> Range<u64> {
+        self.insert_start
+            ..self
+                .insert_start
+                .saturating_add(self.effective_insert_count())
+    }
+    /// The part of the workload run by shard `shard_index` of `shard_count`,
+    /// e.g. one of several load generating processes
+    ///
+    /// The operations and the keys inserted by the load phase are split
+    /// evenly between the shards, with the remainder going to the last one,
+    /// so that together the shards run the whole workload.
+    ///
+    /// Fails if `shard_index` is not below `shard_count`, or if there are
+    /// more shards than records inserted by the load phase or than
+    /// operations, unless `operation_count` is 0 to run until
+    /// `max_execution_time`.
+    pub fn shard(&self, shard_index: u64, shard_count: u64) -> Result<Workload, WorkloadError> {
+        if shard_index >= shard_count {
+            return Err(WorkloadError::ShardOutOfRange {
+                shard_index,
+                shard_count,
+            });
+        }
+        let inserts = self.effective_insert_count();
+        if shard_count > inserts {
+            return Err(WorkloadError::TooManyShards {
+                shard_count,
+                inserts,
+            });
+        }
+        // a shard with no operations would run until the time limit, if any
+        if self.operation_count > 0 && shard_count > self.operation_count {
+            return Err(WorkloadError::TooFewOperations {
+                shard_count,
+                operations: self.operation_count,
+            });
+        }
+        let split = |total: u64| {
+            let share = total / shard_count;
+            let start = share * shard_index;
+            if shard_index == shard_count - 1 {
+                (start, total - start)
+            } else {
+                (start, share)
+            }
+        };
+        let mut shard = self.clone();
+        let (insert_offset, insert_count) = split(inserts);
+        shard.insert_start = self.insert_start.saturating_add(insert_offset);
+        shard.insert_count = insert_count;
+        shard.operation_count = split(self.operation_count).1;
+        Ok(shard)
+    }
+    /// The total length of the values of a record, taking each field at its
+    /// configured length, which is the maximum for uniform and zipfian
+    /// lengths
+    fn estimated_value_bytes(&self) -> Option<u64> {
+        let mut specified = self.field_specs.iter().take(self.field_count as usize);
+        let unspecified = self.field_count - specified.len() as u64;
+        specified.try_fold(unspecified.checked_mul(self.field_length)?, |sum, spec| {
+            sum.checked_add(spec.length)
+        })
+    }
+    /// The total length of the field names, computed without building
+    /// them as there may be very many fields
+    fn field_names_bytes(&self) -> Option<u64> {
+        let prefixes = (self.field_name_prefix.len() as u64).checked_mul(self.field_count)?;
+        // the indices with `digits` digits are `start..end`
+        let mut indices = 0u64;
+        let (mut start, mut digits) = (0u64, 1u64);
+        while start < self.field_count {
+            let end = 10u64.saturating_pow(digits as u32).min(self.field_count);
+            indices = indices.checked_add((end - start).checked_mul(digits)?)?;
+            start = end;
+            digits += 1;
+        }
+        prefixes.checked_add(indices)
+    }
+    /// Approximate size of one record: its key, field names and field values
+    fn estimated_record_bytes(&self) -> Result<u64, WorkloadError> {
+        let key = self.format_key(self.record_count.saturating_sub(1)).len() as u64;
+        self.field_names_bytes()
+            .zip(self.estimated_value_bytes())
+            .and_then(|(names, values)| names.checked_add(values)?.checked_add(key))
+            .ok_or(WorkloadError::Overflow("record size"))
+    }
+    /// Approximate size of the table after the load phase
+    ///
+    /// Fails with [`WorkloadError::Overflow`] if the size does not fit in
+    /// a `u64`.
+    pub fn estimated_dataset_bytes(&self) -> Result<u64, WorkloadError> {
+        self.record_count
+            .checked_mul(self.estimated_record_bytes()?)
+            .ok_or(WorkloadError::Overflow("dataset size"))
+    }
+    /// Approximate number of bytes written by the run phase: inserts write
+    /// a whole record, updates and read-modify-writes write one field (or
+    /// every field if `write_all_fields` is set)
+    ///
+    /// Fails with [`WorkloadError::Overflow`] if the size does not fit in
+    /// a `u64`.
+    pub fn estimated_write_bytes_during_run(&self) -> Result<u64, WorkloadError> {
+        let overflow = || WorkloadError::Overflow("bytes written during the run");
+        let value_bytes = self.estimated_value_bytes().ok_or_else(overflow)?;
+        let update_bytes = if self.write_all_fields || self.field_count == 0 {
+            value_bytes
+        } else {
+            value_bytes / self.field_count
+        };
+        let operations = self.operation_count as f64;
+        let inserts = (self.insert_proportion * operations).round() as u64;
+        let updates = ((self.update_proportion + self.read_modify_write_proportion) * operations)
+            .round() as u64;
+        let insert_bytes = inserts.checked_mul(self.estimated_record_bytes()?);
+        let update_bytes = updates.checked_mul(update_bytes);
+        insert_bytes
+            .zip(update_bytes)
+            .and_then(|(inserts, updates)| inserts.checked_add(updates))
+            .ok_or_else(overflow)
+    }
+    /// The number of operations a driver performs in `phase`
+    pub fn operations_for_phase(&self, phase: Phase) -> u64 {
+        match phase {
+            Phase::Load => self.effective_insert_count(),
+            Phase::Run => self.operation_count,
+        }
+    }
+    /// Random number generator for choosing operations and keys,
+    /// seeded from `seed` if set
+    pub fn rng(&self) -> SmallRng {
+        match self.seed {
+            Some(seed) => SmallRng::seed_from_u64(seed),
+            None => SmallRng::from_entropy(),
+        }
+    }
+    /// Like [`Workload::rng`], but with `salt` mixed into the seed, so that
+    /// each consumer of a seeded workload draws its own stream rather than
+    /// one moving in lockstep with the others
+    pub(crate) fn salted_rng(&self, salt: RngSalt) -> SmallRng {
+        match self.seed {
+            Some(seed) => SmallRng::seed_from_u64(seed ^ fnv_hash64(salt.value())),
+            None => SmallRng::from_entropy(),
+        }
+    }
+    /// The `(operation, key)` pairs a run performs, in order, chosen with
+    /// [`Workload::rng`] so that a seeded workload always yields the same plan
+    ///
+    /// Inserts are assigned keys after the loaded records.
+    ///
+    /// # Panics
+    /// If the workload does not pass [`Workload::validate`]
+    pub fn operation_plan(&self) -> impl Iterator<Item = (Operation, u64)> {
+        let operations = OperationChooser::new(self);
+        // an insert-only run may start from an empty table
+        let mut keys = (self.record_count > 0).then(|| KeyChooser::from_workload(self));
+        let mut rng = self.salted_rng(RngSalt::Operations);
+        let mut next_insert = self.record_count;
+        std::iter::repeat_with(move || {
+            let operation = operations.next_operation(&mut rng);
+            let key = if operation == Operation::Insert {
+                let key = next_insert;
+                next_insert += 1;
+                if let Some(keys) = &mut keys {
+                    keys.acknowledge_insert(key);
+                }
+                key
+            } else {
+                keys.as_mut()
+                    .expect("validate rejects operations on an empty table")
+                    .next_key()
+            };
+            (operation, key)
+        })
+        .take(self.operation_count as usize)
+    }
+    /// A copy of the workload with `record_count`, `operation_count` and
+    /// `insert_count` multiplied by `factor`, rounded to the nearest integer
+    /// but at least 1 and saturating at `u64::MAX`
+    ///
+    /// An `insert_count` of 0 (insert `record_count` records) is kept as is.
+    pub fn scaled(&self, factor: f64) -> Result<Workload, WorkloadError> {
+        if !(factor.is_finite() && factor > 0.0) {
+            return Err(WorkloadError::InvalidScaleFactor(factor));
+        }
+        // float to integer casts saturate
+        let scale = |n: u64| ((n as f64 * factor).round() as u64).max(1);
+        let mut scaled = self.clone();
+        scaled.record_count = scale(self.record_count);
+        scaled.operation_count = scale(self.operation_count);
+        if self.insert_count > 0 {
+            scaled.insert_count = scale(self.insert_count);
+        }
+        Ok(scaled)
+    }
+    /// The table name followed by `_` and `suffix`, e.g. to give
+    /// concurrent runs against the same database their own table
+    pub fn table_name_with_suffix(&self, suffix: &str) -> String {
+        format!("{}_{}", self.table, suffix)
+    }
+    /// A copy of the workload running against table `name`
+    pub fn with_table(&self, name: &str) -> Workload {
+        let mut workload = self.clone();
+        workload.table = name.to_owned();
+        workload
+    }
+    /// Whether the workloads are equal, treating floating-point fields
+    /// within `epsilon` of each other as equal
+    pub fn approx_eq(&self, other: &Workload, epsilon: f64) -> bool {
+        let mut other = other.clone();
+        let mut floats: Vec<_> = vec![
+            (self.read_proportion, &mut other.read_proportion),
+            (self.update_proportion, &mut other.update_proportion),
+            (self.insert_proportion, &mut other.insert_proportion),
+            (
+                self.read_modify_write_proportion,
+                &mut other.read_modify_write_proportion,
+            ),
+            (self.scan_proportion, &mut other.scan_proportion),
+            (self.delete_proportion, &mut other.delete_proportion),
+            (self.hotspot_data_fraction, &mut other.hotspot_data_fraction),
+            (
+                self.hotspot_operation_fraction,
+                &mut other.hotspot_operation_fraction,
+            ),
+            (
+                self.exponential.percentile,
+                &mut other.exponential.percentile,
+            ),
+            (self.exponential.frac, &mut other.exponential.frac),
+            (self.zipfian.constant, &mut other.zipfian.constant),
+            (self.scan.zipfian_constant, &mut other.scan.zipfian_constant),
+        ];
+        // lists of different lengths are left for `==` to tell apart
+        for (mine, theirs) in [
+            (
+                &self.histogram.percentiles,
+                &mut other.histogram.percentiles,
+            ),
+            (
+                &self.hdrhistogram.percentiles,
+                &mut other.hdrhistogram.percentiles,
+            ),
+        ] {
+            if mine.len() == theirs.len() {
+                floats.extend(mine.iter().copied().zip(theirs.iter_mut()));
+            }
+        }
+        for (mine, theirs) in floats {
+            if (mine - *theirs).abs() <= epsilon {
+                *theirs = mine;
+            }
+        }
+        *self == other
+    }
+    /// Check the workload for configuration mistakes
+    pub fn validate(&self) -> Result<(), WorkloadError> {
+        let proportions = OperationProportions::from(self);
+        let floats = [
+            ("hotspotdatafraction", self.hotspot_data_fraction),
+            ("hotspotopnfraction", self.hotspot_operation_fraction),
+            ("exponential.percentile", self.exponential.percentile),
+            ("exponential.frac", self.exponential.frac),
+            ("zipfian.constant", self.zipfian.constant),
+            ("scan.zipfianconstant", self.scan.zipfian_constant),
+        ];
+        let percentiles = self
+            .histogram
+            .percentiles
+            .iter()
+            .map(|p| ("histogram.percentiles", *p))
+            .chain(
+                self.hdrhistogram
+                    .percentiles
+                    .iter()
+                    .map(|p| ("hdrhistogram.percentiles", *p)),
+            );
+        if let Some((field, _)) = proportions
+            .named()
+            .into_iter()
+            .chain(floats)
+            .chain(percentiles)
+            .find(|(_, value)| !value.is_finite())
+        {
+            return Err(WorkloadError::NotFinite { field });
+        }
+        proportions.validate()?;
+        for (field, value) in [
+            ("hotspotdatafraction", self.hotspot_data_fraction),
+            ("hotspotopnfraction", self.hotspot_operation_fraction),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(WorkloadError::FractionOutOfRange { field, value });
+            }
+        }
+        for (field, value) in [
+            ("zipfian.constant", self.zipfian.constant),
+            ("scan.zipfianconstant", self.scan.zipfian_constant),
+        ] {
+            if !(value > 0.0 && value < 1.0) {
+                return Err(WorkloadError::ZipfianConstantOutOfRange { field, value });
+            }
+        }
+        for (field, percentiles) in [
+            ("histogram.percentiles", &self.histogram.percentiles),
+            ("hdrhistogram.percentiles", &self.hdrhistogram.percentiles),
+        ] {
+            if let Some(&value) = percentiles.iter().find(|p| !(**p > 0.0 && **p <= 100.0)) {
+                return Err(WorkloadError::PercentileOutOfRange { field, value });
+            }
+        }
+        if self.timeseries.granularity.is_zero() {
+            return Err(WorkloadError::InvalidValue {
+                key: "timeseries.granularity".to_owned(),
+                value: "0".to_owned(),
+                reason: "expected a positive duration".to_owned(),
+            });
+        }
+        if self.record_count == 0 {
+            let proportions = OperationProportions::from(self);
+            let existing = [
+                (Operation::Read, proportions.read),
+                (Operation::Update, proportions.update),
+                (Operation::Scan, proportions.scan),
+                (Operation::ReadModifyWrite, proportions.read_modify_write),
+                (Operation::Delete, proportions.delete),
+            ];
+            if let Some(&(operation, proportion)) = existing.iter().find(|(_, p)| *p > 0.0) {
+                return Err(WorkloadError::NoRecords {
+                    operation,
+                    proportion,
+                });
+            }
+        }
+        if self.scan_proportion > 0.0 && self.max_scan_length == 0 {
+            return Err(WorkloadError::InvalidScanConfig {
+                scan_proportion: self.scan_proportion,
+            });
+        }
+        if !self.field_specs.is_empty() && self.field_specs.len() as u64 != self.field_count {
+            return Err(WorkloadError::FieldCountMismatch {
+                field_count: self.field_count,
+                specs: self.field_specs.len(),
+            });
+        }
+        let field_lengths = self.field_specs.iter().map(|spec| {
+            (
+                ("field.length", spec.length),
+                ("field.lengthdistribution", spec.length_distribution),
+            )
+        });
+        let global = (
+            ("fieldlength", self.field_length),
+            ("fieldlengthdistribution", self.field_length_distribution),
+        );
+        for ((length_field, length), (field, distribution)) in
+            [global].into_iter().chain(field_lengths)
+        {
+            match distribution {
+                Distribution::Constant => {}
+                Distribution::Uniform | Distribution::Zipfian if length == 0 => {
+                    return Err(WorkloadError::ZeroFieldLength {
+                        field: length_field,
+                        distribution,
+                    })
+                }
+                Distribution::Uniform | Distribution::Zipfian => {}
+                _ => {
+                    return Err(WorkloadError::UnsupportedDistribution {
+                        field,
+                        distribution,
+                    })
+                }
+            }
+        }
+        if !matches!(
+            self.scan_length_distribution,
+            Distribution::Uniform | Distribution::Zipfian
+        ) {
+            return Err(WorkloadError::UnsupportedDistribution {
+                field: "scanlengthdistribution",
+                distribution: self.scan_length_distribution,
+            });
+        }
+        Ok(())
+    }
+
+    /// Human-readable cautions about combinations of settings that are
+    /// valid but unlikely to behave as intended
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.request_distribution == Distribution::Latest
+            && self.read_modify_write_proportion > 0.0
+        {
+            warnings.push(
+                "latest distribution with read-modify-write operations concentrates \
+                 read-modify-writes on the most recent inserts"
+                    .to_owned(),
+            );
+        }
+        if self.request_distribution == Distribution::Constant && self.effective_thread_count() > 1
+        {
+            warnings.push(format!(
+                "constant distribution with {} threads makes every thread contend on the same key",
+                self.effective_thread_count()
+            ));
+        }
+        let threads = self.effective_thread_count();
+        if threads > self.operation_count {
+            warnings.push(format!(
+                "{} threads for {} operations leaves some threads idle during the run phase",
+                threads, self.operation_count
+            ));
+        }
+        if threads > self.effective_insert_count() {
+            warnings.push(format!(
+                "{} threads for {} records leaves some threads idle during the load phase",
+                threads,
+                self.effective_insert_count()
+            ));
+        }
+        warnings
+    }
+
+    /// Start building a workload from the default settings
+    pub fn builder() -> WorkloadBuilder {
+        WorkloadBuilder::default()
+    }
+    /// A workload that only inserts `record_count` records, as the load
+    /// phase does
+    pub fn load_only(record_count: u64) -> Self {
+        WorkloadBuilder::default()
+            .record_count(record_count)
+            .operation_count(record_count)
+            .proportions(OperationProportions {
+                insert: 1.,
+                ..Default::default()
+            })
+            .build()
+            .unwrap()
+    }
+    /// The builder shared by the core workloads, which differ only in
+    /// their proportions and distributions
+    fn core_builder(record_count: u64, operation_count: u64) -> WorkloadBuilder {
+        Workload::builder()
+            .record_count(record_count)
+            .operation_count(operation_count)
+            .read_all_fields(true)
+            .request_distribution(Distribution::Uniform)
+    }
+    pub fn a(record_count: u64, operation_count: u64) -> Self {
+        Self::core_builder(record_count, operation_count)
+            .proportions(StandardWorkload::A.proportions())
+            .build()
+            .unwrap()
+    }
+    pub fn b(record_count: u64, operation_count: u64) -> Self {
+        Self::core_builder(record_count, operation_count)
+            .proportions(StandardWorkload::B.proportions())
+            .build()
+            .unwrap()
+    }
+    pub fn c(record_count: u64, operation_count: u64) -> Self {
+        Self::core_builder(record_count, operation_count)
+            .proportions(StandardWorkload::C.proportions())
+            .build()
+            .unwrap()
+    }
+    pub fn d(record_count: u64, operation_count: u64) -> Self {
+        Self::core_builder(record_count, operation_count)
+            .proportions(StandardWorkload::D.proportions())
+            .request_distribution(Distribution::Latest)
+            .build()
+            .unwrap()
+    }
+    pub fn e(record_count: u64, operation_count: u64) -> Self {
+        Self::core_builder(record_count, operation_count)
+            .proportions(StandardWorkload::E.proportions())
+            .max_scan_length(100)
+            .scan_length_distribution(Distribution::Uniform)
+            .build()
+            .unwrap()
+    }
+    pub fn f(record_count: u64, operation_count: u64) -> Self {
+        Self::core_builder(record_count, operation_count)
+            .proportions(StandardWorkload::F.proportions())
+            .build()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "serde")]
+    use std::fs;
+
+    #[cfg(feature = "serde")]
+    const DEFAULT_CONFIG_STRING: &str = r#"
+workload = "core"
+recordcount = 1000000
+operationcount = 3000000
+threadcount = 500
+target = 0
+insertcount = 0
+insertstart = 0
+fieldcount = 10
+fieldnameprefix = "field"
+fieldlength = 100
+readallfields = true
+writeallfields = false
+dataintegrity = false
+fieldlengthdistribution = "constant"
 readproportion = 0.95
 updateproportion = 0.05
 insertproportion = 0.0
 readmodifywriteproportion = 0.0
 scanproportion = 0.0
+deleteproportion = 0.0
 maxscanlength = 1000
 scanlengthdistribution = "uniform"
 insertorder = "hashed"
+zeropadding = 1
 requestdistribution = "zipfian"
-readcount = 0.2
+hotspotdatafraction = 0.2
 hotspotopnfraction = 0.8
 maxexecutiontime = 0
+core_workload_insertion_retry_limit = 0
+core_workload_insertion_retry_interval = 3
+statusinterval = 10
 table = "usertable"
 columnfamily = ""
 measurementtype = "histogram"
+exportmeasurementsinterval = 0
 
 [histogram]
 buckets = 1000
 
 [timeseries]
-granularity = 1000"#;
+granularity = 1000
+
+[exponential]
+percentile = 95.0
+frac = 0.8571428571
+
+[zipfian]
+constant = 0.99
+
+[scan]
+zipfianconstant = 0.99
+
+[hdrhistogram]
+percentiles = [50.0, 95.0, 99.0, 99.9]"#;
 
     use super::*;
+    #[cfg(feature = "serde")]
     #[test]
     fn workload_serialization() {
         assert_eq!(
@@ -315,6 +1955,7 @@ granularity = 1000"#;
         )
     }
 
+    #[cfg(feature = "serde")]
     #[test]
     fn workload_deserialization() {
         let config_str = fs::read_to_string("workloads/workload_template.toml").unwrap();
@@ -325,51 +1966,1220 @@ granularity = 1000"#;
         );
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn hotspot_data_fraction() {
+        let config = Workload::from_toml_str("hotspotdatafraction = 0.1").unwrap();
+        assert_eq!(config.hotspot_data_fraction, 0.1);
+    }
+
+    #[test]
+    fn validate_proportions() {
+        for workload in [
+            Workload::default(),
+            Workload::a(1000, 1000),
+            Workload::b(1000, 1000),
+            Workload::c(1000, 1000),
+            Workload::d(1000, 1000),
+            Workload::e(1000, 1000),
+            Workload::f(1000, 1000),
+        ] {
+            workload.validate().unwrap();
+        }
+        let config = WorkloadBuilder::default()
+            .read_proportion(0.8)
+            .update_proportion(0.5)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(WorkloadError::ProportionsOutOfRange { .. })
+        ));
+        let config = WorkloadBuilder::default()
+            .read_proportion(1.2)
+            .update_proportion(-0.2)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(WorkloadError::InvalidProportion {
+                field: "readproportion",
+                ..
+            })
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn distribution_round_trip() {
+        for distribution in [
+            Distribution::Constant,
+            Distribution::Uniform,
+            Distribution::Zipfian,
+            Distribution::Latest,
+            Distribution::Exponential,
+            Distribution::ScrambledZipfian,
+            Distribution::Sequential,
+            Distribution::Hotspot,
+        ] {
+            let config = WorkloadBuilder::default()
+                .request_distribution(distribution)
+                .build()
+                .unwrap();
+            let serialized = toml::to_string(&config).unwrap();
+            assert!(serialized.contains(&format!("requestdistribution = \"{}\"", distribution)));
+            assert_eq!(Workload::from_toml_str(&serialized).unwrap(), config);
+            assert_eq!(distribution.as_str().parse(), Ok(distribution));
+        }
+        // new variants are appended so existing orderings are unchanged
+        assert!(Distribution::Zipfian < Distribution::Latest);
+        assert!(Distribution::Latest < Distribution::Sequential);
+        assert!(Distribution::Sequential < Distribution::Hotspot);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn getters() {
+        let config = Workload::from_toml_file("workloads/workloada.toml").unwrap();
+        assert_eq!(config.workload(), "core");
+        assert_eq!(config.record_count(), 1000);
+        assert_eq!(config.read_proportion(), 0.5);
+        assert_eq!(config.request_distribution(), Distribution::Uniform);
+        assert_eq!(config.histogram(), &Workload::default().histogram);
+        assert_eq!(config.histogram().buckets(), Duration::from_secs(1));
+        assert_eq!(config.timeseries().granularity(), Duration::from_secs(1));
+        assert_eq!(config.zipfian().constant(), generator::ZIPFIAN_CONSTANT);
+        assert_eq!(config.hdrhistogram().output_file(), None);
+    }
+
+    #[test]
+    fn max_execution_time() {
+        assert_eq!(Workload::default().max_execution_time(), None);
+        let config = WorkloadBuilder::default()
+            .max_execution_time(Duration::from_secs(60))
+            .build()
+            .unwrap();
+        assert_eq!(config.max_execution_time(), Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn run_stop_condition() {
+        assert_eq!(
+            Workload::a(1000, 500).run_stop_condition(),
+            StopCondition::OperationCount(500)
+        );
+        let minute = Duration::from_secs(60);
+        let config = WorkloadBuilder::default()
+            .operation_count(500)
+            .max_execution_time(minute)
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.run_stop_condition(),
+            StopCondition::Either {
+                ops: 500,
+                time: minute
+            }
+        );
+        let config = WorkloadBuilder::default()
+            .operation_count(0)
+            .max_execution_time(minute)
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.run_stop_condition(),
+            StopCondition::TimeLimit(minute)
+        );
+    }
+
+    #[test]
+    fn field_names() {
+        let config = WorkloadBuilder::default()
+            .field_count(3)
+            .field_name_prefix("col".to_owned())
+            .build()
+            .unwrap();
+        assert_eq!(config.field_names(), vec!["col0", "col1", "col2"]);
+    }
+
+    #[test]
+    fn fields_to_access() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let config = Workload::a(1000, 1000);
+        assert_eq!(config.fields_to_read(&mut rng), (0..10).collect::<Vec<_>>());
+        let written = config.fields_to_write(&mut rng);
+        assert_eq!(written.len(), 1);
+        assert!(written[0] < 10);
+    }
+
+    #[test]
+    fn format_key() {
+        let config = WorkloadBuilder::default().build().unwrap();
+        assert_eq!(config.format_key(42), "user42");
+        let config = WorkloadBuilder::default().zero_padding(10).build().unwrap();
+        assert_eq!(config.format_key(42), "user0000000042");
+        assert_eq!(config.format_key(12345678901), "user12345678901");
+    }
+
+    #[test]
+    fn record_key() {
+        let config = WorkloadBuilder::default()
+            .insert_order(InsertOrder::Ordered)
+            .build()
+            .unwrap();
+        assert_eq!(config.record_key(42), "user42");
+        let config = WorkloadBuilder::default()
+            .insert_order(InsertOrder::Hashed)
+            .build()
+            .unwrap();
+        assert_eq!(config.record_key(42), format!("user{}", fnv_hash64(42)));
+    }
+
+    #[test]
+    fn deterministic_field_value() {
+        let config = WorkloadBuilder::default().field_length(40).build().unwrap();
+        assert_eq!(
+            config.deterministic_field_value(1, 0),
+            b"user1:field0:1621652138:-344534249:-1466"
+        );
+        let config = WorkloadBuilder::default().field_length(8).build().unwrap();
+        assert_eq!(config.deterministic_field_value(1, 0), b"user1:fi");
+        assert_eq!(
+            config.deterministic_field_value_with_length(1, 0, 5),
+            b"user1"
+        );
+        let config = WorkloadBuilder::default()
+            .field_count(2)
+            .field_specs(vec![
+                FieldSpec::new(3, Distribution::Constant),
+                FieldSpec::new(8, Distribution::Constant),
+            ])
+            .build()
+            .unwrap();
+        assert_eq!(config.deterministic_field_value(1, 0), b"use");
+        assert_eq!(config.deterministic_field_value(1, 1), b"user1:fi");
+    }
+
+    #[test]
+    fn per_thread_target() {
+        assert_eq!(Workload::default().per_thread_target_ops_per_sec(), None);
+        let config = WorkloadBuilder::default()
+            .target(1000)
+            .thread_count(8)
+            .build()
+            .unwrap();
+        assert_eq!(config.per_thread_target_ops_per_sec(), Some(125.));
+    }
+
+    #[test]
+    fn operations_for_phase() {
+        let config = Workload::a(1000, 2000);
+        assert_eq!(config.operations_for_phase(Phase::Load), 1000);
+        assert_eq!(config.operations_for_phase(Phase::Run), 2000);
+        assert_eq!(
+            config.operations_for_phase(Phase::from_dotransactions(true)),
+            2000
+        );
+        assert!(!Phase::Load.dotransactions());
+    }
+
+    #[test]
+    fn build_validated() {
+        assert!(matches!(
+            WorkloadBuilder::default()
+                .record_count(10)
+                .build_validated(),
+            Err(WorkloadError::MissingField("operationcount"))
+        ));
+        assert!(matches!(
+            WorkloadBuilder::default()
+                .operation_count(10)
+                .build_validated(),
+            Err(WorkloadError::MissingField("recordcount"))
+        ));
+        assert!(matches!(
+            WorkloadBuilder::default()
+                .record_count(10)
+                .operation_count(10)
+                .read_proportion(0.5)
+                .build_validated(),
+            Err(WorkloadError::ProportionsOutOfRange { .. })
+        ));
+        let config = WorkloadBuilder::default()
+            .record_count(10)
+            .operation_count(20)
+            .build_validated()
+            .unwrap();
+        assert_eq!(config.record_count(), 10);
+        assert_eq!(config.operation_count(), 20);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            Workload::default().to_string(),
+            "workload=core records=1M ops=3M threads=500 R/U/I/S/RMW/D=0.95/0.05/0/0/0/0 dist=zipfian"
+        );
+        assert_eq!(si_count(999), "999");
+        assert_eq!(si_count(1000), "1K");
+        assert_eq!(si_count(2_500_000), "2.5M");
+        assert_eq!(si_count(7_000_000_000), "7G");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_round_trip() {
+        let config = Workload::from_toml_file("workloads/workloada.toml").unwrap();
+        let json = config.to_json_string().unwrap();
+        assert!(json.contains(r#""recordcount":1000"#));
+        assert_eq!(Workload::from_json_str(&json).unwrap(), config);
+        assert_eq!(
+            Workload::from_json_str(r#"{"readproportion": 0.5, "updateproportion": 0.5}"#)
+                .unwrap()
+                .update_proportion(),
+            0.5
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn yaml_round_trip() {
+        let yaml = Workload::default().to_yaml_string().unwrap();
+        assert!(yaml.contains("histogram:\n  buckets: 1000\n"));
+        assert_eq!(Workload::from_yaml_str(&yaml).unwrap(), Workload::default());
+        let config =
+            Workload::from_yaml_str("recordcount: 10\ntimeseries:\n  granularity: 5\n").unwrap();
+        assert_eq!(config.record_count(), 10);
+        assert_eq!(config.timeseries().granularity, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn validate_not_finite() {
+        let config = Workload::builder()
+            .read_proportion(f64::NAN)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(WorkloadError::NotFinite {
+                field: "readproportion"
+            })
+        ));
+        let config = Workload::builder()
+            .exponential(ExponentialConfig {
+                percentile: 95.,
+                frac: f64::INFINITY,
+            })
+            .build()
+            .unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(WorkloadError::NotFinite {
+                field: "exponential.frac"
+            })
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn validate_nan_from_toml() {
+        let config = Workload::from_toml_str("readproportion = nan").unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(WorkloadError::NotFinite {
+                field: "readproportion"
+            })
+        ));
+    }
+
+    #[test]
+    fn validate_hotspot_fractions() {
+        let config = WorkloadBuilder::default()
+            .hotspot_operation_fraction(1.5)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(WorkloadError::FractionOutOfRange {
+                field: "hotspotopnfraction",
+                value,
+            }) if value == 1.5
+        ));
+        let config = WorkloadBuilder::default()
+            .hotspot_data_fraction(-0.1)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(WorkloadError::FractionOutOfRange {
+                field: "hotspotdatafraction",
+                ..
+            })
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn export_file() {
+        assert!(!toml::to_string(&Workload::default())
+            .unwrap()
+            .contains("exportfile"));
+        let config = WorkloadBuilder::default()
+            .export_file("out.txt".to_owned())
+            .build()
+            .unwrap();
+        let serialized = toml::to_string(&config).unwrap();
+        assert!(serialized.contains("exportfile = \"out.txt\""));
+        assert_eq!(
+            Workload::from_toml_str(&serialized).unwrap().export_file(),
+            Some("out.txt")
+        );
+    }
+
+    #[test]
+    fn insert_key_range() {
+        assert_eq!(Workload::a(1000, 1000).insert_key_range(), 0..1000);
+        let config = WorkloadBuilder::default()
+            .record_count(1000)
+            .insert_start(1000)
+            .insert_count(500)
+            .build()
+            .unwrap();
+        assert_eq!(config.insert_key_range(), 1000..1500);
+    }
+
+    #[test]
+    fn shard() {
+        let config = WorkloadBuilder::default()
+            .record_count(1003)
+            .operation_count(2001)
+            .insert_start(10)
+            .build()
+            .unwrap();
+        let shards: Vec<_> = (0..4).map(|i| config.shard(i, 4).unwrap()).collect();
+        assert_eq!(
+            shards.iter().map(Workload::operation_count).sum::<u64>(),
+            2001
+        );
+        assert_eq!(shards[3].operation_count(), 501);
+        let mut next = config.insert_key_range().start;
+        for shard in &shards {
+            assert_eq!(shard.record_count(), 1003);
+            assert_eq!(shard.insert_key_range().start, next);
+            next = shard.insert_key_range().end;
+        }
+        assert_eq!(next, config.insert_key_range().end);
+        assert_eq!(shards[3].insert_key_range(), 760..1013);
+        assert_eq!(config.shard(0, 1).unwrap(), {
+            let mut whole = config.clone();
+            whole.insert_count = 1003;
+            whole
+        });
+    }
+
+    #[test]
+    fn shard_out_of_range() {
+        let config = Workload::a(10, 10);
+        assert!(matches!(
+            config.shard(2, 2),
+            Err(WorkloadError::ShardOutOfRange {
+                shard_index: 2,
+                shard_count: 2
+            })
+        ));
+        assert!(matches!(
+            config.shard(0, 0),
+            Err(WorkloadError::ShardOutOfRange { .. })
+        ));
+        assert!(matches!(
+            config.shard(0, 11),
+            Err(WorkloadError::TooManyShards {
+                shard_count: 11,
+                inserts: 10
+            })
+        ));
+    }
+
+    #[test]
+    fn shard_with_time_limit() {
+        let minute = Duration::from_secs(60);
+        let config = WorkloadBuilder::default()
+            .record_count(100)
+            .operation_count(3)
+            .max_execution_time(minute)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            config.shard(0, 4),
+            Err(WorkloadError::TooFewOperations {
+                shard_count: 4,
+                operations: 3
+            })
+        ));
+        for i in 0..3 {
+            assert!(matches!(
+                config.shard(i, 3).unwrap().run_stop_condition(),
+                StopCondition::Either { ops: 1, .. }
+            ));
+        }
+        let config = WorkloadBuilder::default()
+            .record_count(100)
+            .operation_count(0)
+            .max_execution_time(minute)
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.shard(3, 4).unwrap().run_stop_condition(),
+            StopCondition::TimeLimit(minute)
+        );
+    }
+
+    #[test]
+    fn validate_field_length_distribution() {
+        for distribution in [Distribution::Latest, Distribution::Sequential] {
+            let config = WorkloadBuilder::default()
+                .field_length_distribution(distribution)
+                .build()
+                .unwrap();
+            assert!(matches!(
+                config.validate(),
+                Err(WorkloadError::UnsupportedDistribution {
+                    field: "fieldlengthdistribution",
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn validate_zero_field_length() {
+        let zero = |distribution| {
+            WorkloadBuilder::default()
+                .field_length(0)
+                .field_length_distribution(distribution)
+                .build()
+                .unwrap()
+        };
+        assert!(zero(Distribution::Constant).validate().is_ok());
+        for distribution in [Distribution::Uniform, Distribution::Zipfian] {
+            assert!(matches!(
+                zero(distribution).validate(),
+                Err(WorkloadError::ZeroFieldLength {
+                    field: "fieldlength",
+                    ..
+                })
+            ));
+        }
+        let config = WorkloadBuilder::default()
+            .field_count(2)
+            .field_specs(vec![
+                FieldSpec::new(0, Distribution::Constant),
+                FieldSpec::new(0, Distribution::Uniform),
+            ])
+            .build()
+            .unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(WorkloadError::ZeroFieldLength {
+                field: "field.length",
+                distribution: Distribution::Uniform
+            })
+        ));
+    }
+
+    #[test]
+    fn validate_timeseries_granularity() {
+        let config = WorkloadBuilder::default()
+            .timeseries_granularity(Duration::ZERO)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(WorkloadError::InvalidValue { key, .. }) if key == "timeseries.granularity"
+        ));
+        #[cfg(feature = "serde")]
+        assert!(Workload::from_toml_str("[timeseries]\ngranularity = 0")
+            .unwrap()
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn validate_empty_table() {
+        let config = WorkloadBuilder::default().record_count(0).build().unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(WorkloadError::NoRecords {
+                operation: Operation::Read,
+                ..
+            })
+        ));
+        let config = WorkloadBuilder::default()
+            .record_count(0)
+            .operation_count(10)
+            .proportions(OperationProportions {
+                insert: 1.0,
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+        assert!(config.validate().is_ok());
+        let plan: Vec<_> = config.operation_plan().collect();
+        assert_eq!(
+            plan,
+            (0..10)
+                .map(|key| (Operation::Insert, key))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn validate_scan_config() {
+        assert!(Workload::e(1000, 1000).validate().is_ok());
+        let config = WorkloadBuilder::default()
+            .proportions(StandardWorkload::E.proportions())
+            .max_scan_length(0)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(WorkloadError::InvalidScanConfig { .. })
+        ));
+    }
+
+    #[test]
+    fn warnings() {
+        assert!(Workload::d(1000, 1000).warnings().is_empty());
+        let config = WorkloadBuilder::default()
+            .request_distribution(Distribution::Latest)
+            .proportions(StandardWorkload::F.proportions())
+            .build()
+            .unwrap();
+        assert_eq!(config.warnings().len(), 1);
+        let config = WorkloadBuilder::default()
+            .request_distribution(Distribution::Constant)
+            .thread_count(4)
+            .build()
+            .unwrap();
+        assert_eq!(config.warnings().len(), 1);
+        let config = Workload::a(100, 100);
+        assert_eq!(config.warnings().len(), 2);
+        let config = Workload::a(1000, 100);
+        assert_eq!(config.warnings().len(), 1);
+        assert!(config.warnings()[0].contains("run phase"));
+    }
+
+    #[test]
+    fn salted_rng() {
+        let config = WorkloadBuilder::default().seed(7).build().unwrap();
+        let draws = |mut rng: SmallRng| -> Vec<u64> { (0..8).map(|_| rng.gen()).collect() };
+        let operations = draws(config.salted_rng(RngSalt::Operations));
+        assert_eq!(operations, draws(config.salted_rng(RngSalt::Operations)));
+        assert_ne!(operations, draws(config.salted_rng(RngSalt::Requests)));
+        assert_ne!(operations, draws(config.rng()));
+    }
+
+    #[test]
+    fn operation_plan() {
+        let config = WorkloadBuilder::default()
+            .record_count(100)
+            .operation_count(1000)
+            .proportions(StandardWorkload::D.proportions())
+            .request_distribution(Distribution::Latest)
+            .seed(7)
+            .build()
+            .unwrap();
+        let plan: Vec<_> = config.operation_plan().collect();
+        assert_eq!(plan.len(), 1000);
+        assert_eq!(plan, config.operation_plan().collect::<Vec<_>>());
+        let inserts: Vec<_> = plan
+            .iter()
+            .filter(|(operation, _)| *operation == Operation::Insert)
+            .map(|(_, key)| *key)
+            .collect();
+        assert!(!inserts.is_empty());
+        assert_eq!(
+            inserts,
+            (100..100 + inserts.len() as u64).collect::<Vec<_>>()
+        );
+        assert!(plan
+            .iter()
+            .all(|(_, key)| *key < 100 + inserts.len() as u64));
+    }
+
+    #[test]
+    fn estimated_bytes() {
+        let config = WorkloadBuilder::default()
+            .record_count(1000)
+            .operation_count(1000)
+            .field_count(2)
+            .field_length(100)
+            .zero_padding(1)
+            .proportions(OperationProportions {
+                read: 0.5,
+                update: 0.3,
+                insert: 0.2,
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+        // "user999" + "field0" + "field1" + 2 * 100
+        assert_eq!(config.estimated_dataset_bytes().unwrap(), 1000 * 219);
+        assert_eq!(
+            config.estimated_write_bytes_during_run().unwrap(),
+            200 * 219 + 300 * 100
+        );
+        let config = WorkloadBuilder::default()
+            .record_count(1000)
+            .operation_count(1000)
+            .field_count(2)
+            .field_specs(vec![
+                FieldSpec::new(50, Distribution::Constant),
+                FieldSpec::new(100, Distribution::Uniform),
+            ])
+            .zero_padding(1)
+            .proportions(OperationProportions {
+                read: 0.5,
+                update: 0.3,
+                insert: 0.2,
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+        assert_eq!(config.estimated_dataset_bytes().unwrap(), 1000 * 169);
+        assert_eq!(
+            config.estimated_write_bytes_during_run().unwrap(),
+            200 * 169 + 300 * 75
+        );
+    }
+
+    #[test]
+    fn field_names_bytes() {
+        for field_count in [0, 1, 9, 10, 11, 100, 1234] {
+            let config = WorkloadBuilder::default()
+                .field_count(field_count)
+                .build()
+                .unwrap();
+            let names: usize = config.field_names().iter().map(String::len).sum();
+            assert_eq!(config.field_names_bytes(), Some(names as u64));
+        }
+        let config = WorkloadBuilder::default()
+            .field_count(u64::MAX)
+            .field_name_prefix(String::new())
+            .build()
+            .unwrap();
+        assert_eq!(config.field_names_bytes(), None);
+    }
+
+    #[test]
+    fn estimated_bytes_overflow() {
+        let config = WorkloadBuilder::default()
+            .record_count(u64::MAX / 2)
+            .operation_count(u64::MAX / 2)
+            .field_count(10)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            config.estimated_dataset_bytes(),
+            Err(WorkloadError::Overflow("dataset size"))
+        ));
+        assert!(matches!(
+            config.estimated_write_bytes_during_run(),
+            Err(WorkloadError::Overflow(_))
+        ));
+        let config = WorkloadBuilder::default()
+            .field_count(2)
+            .field_length(u64::MAX)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            config.estimated_dataset_bytes(),
+            Err(WorkloadError::Overflow("record size"))
+        ));
+        // too many fields to hold their names in memory
+        let config = WorkloadBuilder::default()
+            .field_count(u64::MAX / 2)
+            .field_length(0)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            config.estimated_dataset_bytes(),
+            Err(WorkloadError::Overflow("record size"))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn from_toml_reader() {
+        assert_eq!(
+            Workload::from_toml_reader(DEFAULT_CONFIG_STRING.as_bytes()).unwrap(),
+            Workload::default()
+        );
+    }
+
+    #[test]
+    fn effective_thread_count() {
+        let config = WorkloadBuilder::default().thread_count(8).build().unwrap();
+        assert_eq!(config.effective_thread_count(), 8);
+        let config = WorkloadBuilder::default().thread_count(0).build().unwrap();
+        assert!(config.effective_thread_count() >= 1);
+        assert_eq!(config.thread_count(), 0);
+    }
+
+    #[test]
+    fn validate_zipfian_constant() {
+        let config = WorkloadBuilder::default()
+            .zipfian_constant(0.5)
+            .build()
+            .unwrap();
+        assert_eq!(config.zipfian().constant(), 0.5);
+        assert!(config.validate().is_ok());
+        for constant in [0.0, 1.0, 1.5, -0.5] {
+            let config = WorkloadBuilder::default()
+                .zipfian_constant(constant)
+                .build()
+                .unwrap();
+            assert!(matches!(
+                config.validate(),
+                Err(WorkloadError::ZipfianConstantOutOfRange {
+                    field: "zipfian.constant",
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn approx_eq() {
+        let a = Workload::f(1000, 1000);
+        let b = WorkloadBuilder::default()
+            .record_count(1000)
+            .operation_count(1000)
+            .read_all_fields(true)
+            .read_proportion(0.5 + 1e-12)
+            .read_modify_write_proportion(0.5)
+            .update_proportion(0.)
+            .insert_proportion(0.)
+            .scan_proportion(0.)
+            .request_distribution(Distribution::Uniform)
+            .build()
+            .unwrap();
+        assert_ne!(a, b);
+        assert!(a.approx_eq(&b, 1e-9));
+        assert!(!a.approx_eq(&Workload::f(1000, 2000), 1e-9));
+        assert!(!a.approx_eq(&Workload::a(1000, 1000), 1e-9));
+
+        let percentiles = |histogram: Vec<f64>, hdrhistogram: Vec<f64>| {
+            let mut workload = a.clone();
+            workload.histogram.percentiles = histogram;
+            workload.hdrhistogram.percentiles = hdrhistogram;
+            workload
+        };
+        let p = percentiles(vec![95., 99.], vec![99.9]);
+        assert!(p.approx_eq(&percentiles(vec![95. + 1e-12, 99.], vec![99.9]), 1e-9));
+        assert!(p.approx_eq(&percentiles(vec![95., 99.], vec![99.9 - 1e-12]), 1e-9));
+        assert!(!p.approx_eq(&percentiles(vec![95., 99.9], vec![99.9]), 1e-9));
+        assert!(!p.approx_eq(&percentiles(vec![95.], vec![99.9]), 1e-9));
+        assert!(!p.approx_eq(&percentiles(vec![95., 99.], vec![99.]), 1e-9));
+        assert!(!p.approx_eq(&percentiles(vec![95., 99.], vec![]), 1e-9));
+    }
+
+    #[test]
+    fn builder() {
+        assert_eq!(Workload::builder().build().unwrap(), Workload::default());
+        let config = Workload::builder().record_count(10).build().unwrap();
+        assert_eq!(config.record_count(), 10);
+    }
+
+    #[test]
+    fn builder_fork() {
+        let base = WorkloadBuilder::default()
+            .record_count(1000)
+            .operation_count(1000)
+            .read_all_fields(true)
+            .request_distribution(Distribution::Uniform);
+        let a = base
+            .fork()
+            .proportions(StandardWorkload::A.proportions())
+            .build()
+            .unwrap();
+        let c = base
+            .proportions(StandardWorkload::C.proportions())
+            .build()
+            .unwrap();
+        assert_eq!(a, Workload::a(1000, 1000));
+        assert_eq!(c, Workload::c(1000, 1000));
+    }
+
+    #[test]
+    fn enums_as_map_keys() {
+        use std::collections::HashMap;
+        let mut by_distribution = HashMap::new();
+        for workload in [Workload::a(1, 1), Workload::b(1, 1), Workload::d(1, 1)] {
+            *by_distribution
+                .entry(workload.request_distribution())
+                .or_insert(0) += 1;
+        }
+        assert_eq!(by_distribution[&Distribution::Uniform], 2);
+        assert_eq!(by_distribution[&Distribution::Latest], 1);
+        let orders: HashMap<_, _> = [(InsertOrder::Hashed, 1), (InsertOrder::Ordered, 2)].into();
+        assert_eq!(orders[&InsertOrder::Ordered], 2);
+        let types: HashMap<_, _> = [(MeasurementType::Raw, "raw")].into();
+        assert_eq!(types[&MeasurementType::Raw], "raw");
+    }
+
+    #[test]
+    fn distributions() {
+        assert_eq!(
+            Workload::d(1000, 1000).distributions(),
+            Distributions {
+                request: Distribution::Latest,
+                field_length: Distribution::Constant,
+                scan_length: Distribution::Uniform,
+            }
+        );
+    }
+
+    #[test]
+    fn hot_set_range() {
+        let config = WorkloadBuilder::default()
+            .record_count(1000)
+            .hotspot_data_fraction(0.1)
+            .build()
+            .unwrap();
+        assert_eq!(config.hot_set_range(), 0..100);
+        let config = WorkloadBuilder::default()
+            .record_count(1000)
+            .hotspot_data_fraction(1.0)
+            .build()
+            .unwrap();
+        assert_eq!(config.hot_set_range(), 0..1000);
+        let config = WorkloadBuilder::default()
+            .record_count(1000)
+            .hotspot_data_fraction(0.0)
+            .build()
+            .unwrap();
+        assert!(config.hot_set_range().is_empty());
+    }
+
+    #[test]
+    fn with_table() {
+        let config = Workload::a(1000, 1000);
+        assert_eq!(config.table_name_with_suffix("42"), "usertable_42");
+        let renamed = config.with_table(&config.table_name_with_suffix("42"));
+        assert_eq!(renamed.table(), "usertable_42");
+        assert_eq!(renamed.with_table("usertable"), config);
+    }
+
+    #[test]
+    fn scaled() {
+        let config = Workload::default().scaled(0.01).unwrap();
+        assert_eq!(config.record_count(), 10_000);
+        assert_eq!(config.operation_count(), 30_000);
+        assert_eq!(config.insert_count(), 0);
+        assert_eq!(
+            config.read_proportion(),
+            Workload::default().read_proportion()
+        );
+        let config = Workload::a(10, 10).scaled(1e-6).unwrap();
+        assert_eq!(config.record_count(), 1);
+        let config = Workload::a(10, 10).scaled(1e300).unwrap();
+        assert_eq!(config.record_count(), u64::MAX);
+        for factor in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                Workload::default().scaled(factor),
+                Err(WorkloadError::InvalidScaleFactor(_))
+            ));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn summary_json() {
+        let summary = Workload::e(1000, 2000).summary_json();
+        let summary = summary.as_object().unwrap();
+        assert_eq!(summary["records"], 1000);
+        assert_eq!(summary["operations"], 2000);
+        assert_eq!(summary["scan_proportion"], 0.95);
+        assert_eq!(summary["request_distribution"], "uniform");
+        assert!(summary.values().all(|value| !value.is_object()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn status_interval_names() {
+        let expected = WorkloadBuilder::default()
+            .status_interval(Duration::from_secs(5))
+            .build()
+            .unwrap();
+        for toml in ["statusinterval = 5", "\"status.interval\" = 5"] {
+            assert_eq!(Workload::from_toml_str(toml).unwrap(), expected);
+        }
+        for json in ["{\"statusinterval\": 5}", "{\"status.interval\": 5}"] {
+            assert_eq!(Workload::from_json_str(json).unwrap(), expected);
+        }
+        for properties in ["statusinterval=5", "status.interval=5"] {
+            assert_eq!(Workload::from_properties_str(properties).unwrap(), expected);
+        }
+        let partial: PartialWorkload = toml::from_str("\"status.interval\" = 5").unwrap();
+        assert_eq!(partial.status_interval, Some(Duration::from_secs(5)));
+        // a key copied from one format's output is accepted by the other
+        for (key, value) in expected.non_default_fields() {
+            let toml = format!("\"{}\" = {}", key, value);
+            assert_eq!(Workload::from_toml_str(&toml).unwrap(), expected);
+        }
+        let toml = toml::to_string(&expected).unwrap();
+        let line = toml
+            .lines()
+            .find(|line| line.starts_with("statusinterval"))
+            .unwrap();
+        assert_eq!(Workload::from_properties_str(line).unwrap(), expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn write_toml() {
+        let config = Workload::e(1000, 1000);
+        let mut out = vec![];
+        config.write_toml(&mut out).unwrap();
+        let toml = String::from_utf8(out).unwrap();
+        assert_eq!(toml, toml::to_string(&config).unwrap());
+        assert_eq!(Workload::from_toml_str(&toml).unwrap(), config);
+
+        struct Closed;
+        impl io::Write for Closed {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::BrokenPipe.into())
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+        assert!(matches!(
+            config.write_toml(Closed),
+            Err(WorkloadError::Write(_))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn slo() {
+        let config = Workload::from_toml_str("[slo]\nread = 10\nreadmodifywrite = 25").unwrap();
+        assert_eq!(
+            config.slo(),
+            &SloConfig::default()
+                .with_p99(Operation::Read, Duration::from_millis(10))
+                .with_p99(Operation::ReadModifyWrite, Duration::from_millis(25))
+        );
+        assert_eq!(config.slo().p99(Operation::Update), None);
+        let toml = toml::to_string(&config).unwrap();
+        assert!(toml.contains("[slo]\nread = 10\nreadmodifywrite = 25\n"));
+        assert_eq!(Workload::from_toml_str(&toml).unwrap(), config);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn missing_measurement_tables() {
+        let config = Workload::from_toml_str("recordcount = 10").unwrap();
+        assert_eq!(config.histogram(), &HistogramConfig::default());
+        assert_eq!(config.timeseries(), &TimeseriesConfig::default());
+        let config =
+            Workload::from_toml_str("[histogram]\npercentiles = [50]\n[timeseries]").unwrap();
+        assert_eq!(config.histogram().buckets(), Duration::from_millis(1000));
+        assert_eq!(config.histogram().percentiles(), [50.]);
+        assert_eq!(config.timeseries(), &TimeseriesConfig::default());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn histogram_percentiles() {
+        let config =
+            Workload::from_toml_str("[histogram]\nbuckets = 100\npercentiles = \"50, 99.9\"")
+                .unwrap();
+        assert_eq!(config.histogram().percentiles(), [50., 99.9]);
+        assert!(config.validate().is_ok());
+        let config =
+            Workload::from_toml_str("[histogram]\nbuckets = 100\npercentiles = [50, 99.9]")
+                .unwrap();
+        assert_eq!(config.histogram().percentiles(), [50., 99.9]);
+        assert!(
+            Workload::from_toml_str("[histogram]\nbuckets = 100\npercentiles = \"50,x\"").is_err()
+        );
+        let config =
+            Workload::from_toml_str("[histogram]\nbuckets = 100\npercentiles = [0, 50]").unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(WorkloadError::PercentileOutOfRange {
+                field: "histogram.percentiles",
+                value
+            }) if value == 0.0
+        ));
+    }
+
+    #[test]
+    fn load_only() {
+        let config = Workload::load_only(1000);
+        assert!(config.validate().is_ok());
+        assert_eq!(config.operation_count(), 1000);
+        assert_eq!(
+            config.expected_operation_counts(),
+            OperationCounts {
+                inserts: 1000,
+                ..Default::default()
+            }
+        );
+        assert_eq!(config.insert_order(), Workload::default().insert_order());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn field_specs() {
+        let config = Workload::from_toml_str(
+            r#"
+fieldcount = 3
+
+[[field]]
+length = 4096
+lengthdistribution = "uniform"
+
+[[field]]
+length = 10
+
+[[field]]
+length = 20
+"#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.field_spec(0),
+            FieldSpec::new(4096, Distribution::Uniform)
+        );
+        assert_eq!(
+            config.field_spec(1),
+            FieldSpec::new(10, Distribution::Constant)
+        );
+        assert_eq!(
+            Workload::from_toml_str(&toml::to_string(&config).unwrap()).unwrap(),
+            config
+        );
+        let config = Workload::default();
+        assert_eq!(
+            config.field_spec(5),
+            FieldSpec::new(100, Distribution::Constant)
+        );
+        let config = WorkloadBuilder::default()
+            .field_specs(vec![FieldSpec::new(1, Distribution::Constant)])
+            .build()
+            .unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(WorkloadError::FieldCountMismatch {
+                field_count: 10,
+                specs: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn effective_insert_count() {
+        let config = Workload::a(1000, 2000);
+        assert_eq!(config.effective_insert_count(), 1000);
+        let config = WorkloadBuilder::default()
+            .record_count(1000)
+            .insert_count(250)
+            .build()
+            .unwrap();
+        assert_eq!(config.effective_insert_count(), 250);
+        assert_eq!(config.operations_for_phase(Phase::Load), 250);
+    }
+
+    #[test]
+    fn nested_config_setters() {
+        let config = WorkloadBuilder::default()
+            .measurement_type(MeasurementType::Timeseries)
+            .histogram_buckets(Duration::from_millis(200))
+            .timeseries_granularity(Duration::from_millis(10))
+            .build()
+            .unwrap();
+        assert_eq!(config.measurement_type(), MeasurementType::Timeseries);
+        assert_eq!(config.histogram().buckets(), Duration::from_millis(200));
+        assert_eq!(config.timeseries().granularity(), Duration::from_millis(10));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn malformed_toml() {
+        assert!(matches!(
+            Workload::from_toml_str("recordcount = \"many\""),
+            Err(WorkloadError::Toml(_))
+        ));
+        let err = Workload::from_toml_file("workloads/nonexistent.toml").unwrap_err();
+        assert!(matches!(err, WorkloadError::ReadFile { .. }));
+        assert!(err
+            .to_string()
+            .starts_with("failed to read workload at workloads/nonexistent.toml: "));
+        let path = std::env::temp_dir().join("yc3b_malformed_toml.toml");
+        fs::write(&path, "recordcount = \"many\"").unwrap();
+        let err = Workload::from_toml_file(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(err, WorkloadError::ParseFile { .. }));
+        assert!(err
+            .to_string()
+            .starts_with(&format!("failed to parse workload at {}: ", path.display())));
+    }
+
+    #[cfg(feature = "serde")]
     #[test]
     fn workloada() {
         assert_eq!(
             toml::to_string(&Workload::a(1000, 1000)),
-            toml::to_string(&Workload::from_toml_file("workloads/workloada.toml"))
+            toml::to_string(&Workload::from_toml_file("workloads/workloada.toml").unwrap())
         )
     }
 
+    #[cfg(feature = "serde")]
     #[test]
     fn workloadb() {
         assert_eq!(
             toml::to_string(&Workload::b(1000, 1000)),
-            toml::to_string(&Workload::from_toml_file("workloads/workloadb.toml"))
+            toml::to_string(&Workload::from_toml_file("workloads/workloadb.toml").unwrap())
         )
     }
 
+    #[cfg(feature = "serde")]
     #[test]
     fn workloadc() {
         assert_eq!(
             toml::to_string(&Workload::c(1000, 1000)),
-            toml::to_string(&Workload::from_toml_file("workloads/workloadc.toml"))
+            toml::to_string(&Workload::from_toml_file("workloads/workloadc.toml").unwrap())
         )
     }
 
+    #[cfg(feature = "serde")]
     #[test]
     fn workloadd() {
         assert_eq!(
             toml::to_string(&Workload::d(1000, 1000)),
-            toml::to_string(&Workload::from_toml_file("workloads/workloadd.toml"))
+            toml::to_string(&Workload::from_toml_file("workloads/workloadd.toml").unwrap())
         )
     }
 
+    #[cfg(feature = "serde")]
     #[test]
     fn workloade() {
         assert_eq!(
             toml::to_string(&Workload::e(1000, 1000)),
-            toml::to_string(&Workload::from_toml_file("workloads/workloade.toml"))
+            toml::to_string(&Workload::from_toml_file("workloads/workloade.toml").unwrap())
         )
     }
 
+    #[cfg(feature = "serde")]
     #[test]
     fn workloadf() {
         assert_eq!(
             toml::to_string(&Workload::f(1000, 1000)),
-            toml::to_string(&Workload::from_toml_file("workloads/workloadf.toml"))
+            toml::to_string(&Workload::from_toml_file("workloads/workloadf.toml").unwrap())
         )
     }
 }
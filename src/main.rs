@@ -9,9 +9,9 @@ use std::thread;
 use std::time::Instant;
 use structopt::StructOpt;
 use workload::CoreWorkload;
+use yc3b::generator;
 
 pub mod db;
-pub mod generator;
 pub mod properties;
 pub mod sqlite;
 pub mod workload;
@@ -0,0 +1,7 @@
+mod histogram;
+mod report;
+mod timeseries;
+
+pub use histogram::Histogram;
+pub use report::write_histogram_report;
+pub use timeseries::Timeseries;
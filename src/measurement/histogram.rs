@@ -0,0 +1,169 @@
+use std::time::Duration;
+
+use crate::HistogramConfig;
+
+const BUCKET_WIDTH: Duration = Duration::from_millis(1);
+
+/// The percentiles reported when none are configured, as in YCSB
+const DEFAULT_PERCENTILES: [f64; 2] = [95., 99.];
+
+/// Latency histogram with 1ms wide buckets up to the configured range and
+/// a single overflow bucket for anything slower
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    buckets: Vec<u64>,
+    overflow: u64,
+    count: u64,
+    total: Duration,
+    min: Option<Duration>,
+    max: Option<Duration>,
+    /// The percentiles included in [`Histogram::percentiles`]
+    percentiles: Vec<f64>,
+}
+
+impl Histogram {
+    pub fn new(config: HistogramConfig) -> Self {
+        let buckets = (config.buckets.as_millis() / BUCKET_WIDTH.as_millis()) as usize;
+        let percentiles = if config.percentiles.is_empty() {
+            DEFAULT_PERCENTILES.to_vec()
+        } else {
+            config.percentiles
+        };
+        Self {
+            percentiles,
+            buckets: vec![0; buckets],
+            overflow: 0,
+            count: 0,
+            total: Duration::ZERO,
+            min: None,
+            max: None,
+        }
+    }
+
+    pub fn record(&mut self, latency: Duration) {
+        let bucket = (latency.as_micros() / BUCKET_WIDTH.as_micros()) as usize;
+        match self.buckets.get_mut(bucket) {
+            Some(count) => *count += 1,
+            None => self.overflow += 1,
+        }
+        self.count += 1;
+        self.total += latency;
+        self.min = Some(self.min.map_or(latency, |min| min.min(latency)));
+        self.max = Some(self.max.map_or(latency, |max| max.max(latency)));
+    }
+
+    /// The number of recorded latencies
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The number of latencies in each 1ms wide bucket
+    pub fn bucket_counts(&self) -> &[u64] {
+        &self.buckets
+    }
+
+    /// The number of latencies beyond the last bucket
+    pub fn overflow(&self) -> u64 {
+        self.overflow
+    }
+
+    pub fn min(&self) -> Duration {
+        self.min.unwrap_or_default()
+    }
+
+    pub fn max(&self) -> Duration {
+        self.max.unwrap_or_default()
+    }
+
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_nanos((self.total.as_nanos() / self.count as u128) as u64)
+    }
+
+    /// The lower bound of the bucket holding the `p`th percentile latency,
+    /// or the maximum latency if it falls in the overflow bucket
+    pub fn percentile(&self, p: f64) -> Duration {
+        let threshold = self.count as f64 * p / 100.0;
+        let mut seen = 0;
+        for (bucket, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen > 0 && seen as f64 >= threshold {
+                return BUCKET_WIDTH * bucket as u32;
+            }
+        }
+        self.max()
+    }
+
+    /// The configured percentiles along with their latency
+    pub fn percentiles(&self) -> Vec<(f64, Duration)> {
+        self.percentiles
+            .iter()
+            .map(|&p| (p, self.percentile(p)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn histogram() -> Histogram {
+        Histogram::new(HistogramConfig {
+            buckets: Duration::from_millis(1000),
+            percentiles: vec![],
+        })
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let mut histogram = histogram();
+        for ms in 1..=100 {
+            histogram.record(Duration::from_millis(ms));
+        }
+        assert_eq!(histogram.count(), 100);
+        assert_eq!(histogram.percentile(50.), Duration::from_millis(50));
+        assert_eq!(histogram.percentile(99.), Duration::from_millis(99));
+        assert_eq!(histogram.mean(), Duration::from_micros(50500));
+        assert_eq!(histogram.min(), Duration::from_millis(1));
+        assert_eq!(histogram.max(), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut histogram = histogram();
+        histogram.record(Duration::from_micros(300));
+        histogram.record(Duration::from_secs(5));
+        assert_eq!(histogram.percentile(50.), Duration::ZERO);
+        assert_eq!(histogram.percentile(99.), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_empty() {
+        let histogram = histogram();
+        assert_eq!(histogram.mean(), Duration::ZERO);
+        assert_eq!(histogram.percentile(99.), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_configured_percentiles() {
+        let mut histogram = histogram();
+        for ms in 1..=100 {
+            histogram.record(Duration::from_millis(ms));
+        }
+        assert_eq!(
+            histogram.percentiles(),
+            [
+                (95., Duration::from_millis(95)),
+                (99., Duration::from_millis(99))
+            ]
+        );
+        let mut histogram = Histogram::new(HistogramConfig {
+            buckets: Duration::from_millis(1000),
+            percentiles: vec![50.],
+        });
+        histogram.record(Duration::from_millis(7));
+        assert_eq!(histogram.percentiles(), [(50., Duration::from_millis(7))]);
+    }
+}
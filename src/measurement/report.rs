@@ -0,0 +1,134 @@
+use std::io::{self, Write};
+
+use super::Histogram;
+use crate::{MeasurementType, Operation, Workload};
+
+/// `p` as YCSB labels percentiles: `95th`, `99.9`
+fn ordinal(p: f64) -> String {
+    if p.fract() != 0.0 {
+        return p.to_string();
+    }
+    let n = p as u64;
+    let suffix = match (n % 100, n % 10) {
+        (11..=13, _) => "th",
+        (_, 1) => "st",
+        (_, 2) => "nd",
+        (_, 3) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
+
+/// Write the latencies of `operation` in the format of YCSB's histogram
+/// measurement, e.g. `[READ], AverageLatency(us), 512.3`, so that tools
+/// parsing YCSB's output can read it
+///
+/// The per-bucket counts are only written if the measurement type of
+/// `workload` is `histogram`, as in YCSB.
+pub fn write_histogram_report<W: Write>(
+    operation: Operation,
+    histogram: &Histogram,
+    workload: &Workload,
+    mut writer: W,
+) -> io::Result<()> {
+    let label = operation.as_str();
+    writeln!(writer, "[{}], Operations, {}", label, histogram.count())?;
+    writeln!(
+        writer,
+        "[{}], AverageLatency(us), {}",
+        label,
+        histogram.mean().as_nanos() as f64 / 1000.0
+    )?;
+    writeln!(
+        writer,
+        "[{}], MinLatency(us), {}",
+        label,
+        histogram.min().as_micros()
+    )?;
+    writeln!(
+        writer,
+        "[{}], MaxLatency(us), {}",
+        label,
+        histogram.max().as_micros()
+    )?;
+    for (p, latency) in histogram.percentiles() {
+        writeln!(
+            writer,
+            "[{}], {}PercentileLatency(us), {}",
+            label,
+            ordinal(p),
+            latency.as_micros()
+        )?;
+    }
+    if workload.measurement_type() == MeasurementType::Histogram {
+        for (bucket, count) in histogram.bucket_counts().iter().enumerate() {
+            writeln!(writer, "[{}], {}, {}", label, bucket, count)?;
+        }
+        writeln!(
+            writer,
+            "[{}], >{}, {}",
+            label,
+            histogram.bucket_counts().len(),
+            histogram.overflow()
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{HistogramConfig, WorkloadBuilder};
+
+    #[test]
+    fn ordinals() {
+        let ordinals: Vec<_> = [1., 2., 3., 11., 12., 21., 95., 99.9]
+            .into_iter()
+            .map(ordinal)
+            .collect();
+        assert_eq!(
+            ordinals,
+            ["1st", "2nd", "3rd", "11th", "12th", "21st", "95th", "99.9"]
+        );
+    }
+
+    #[test]
+    fn ycsb_format() {
+        let mut histogram = Histogram::new(HistogramConfig {
+            buckets: Duration::from_millis(2),
+            percentiles: vec![],
+        });
+        histogram.record(Duration::from_micros(500));
+        histogram.record(Duration::from_micros(1500));
+        histogram.record(Duration::from_millis(5));
+        let workload = Workload::default();
+        let mut out = vec![];
+        write_histogram_report(Operation::Read, &histogram, &workload, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "[READ], Operations, 3
+[READ], AverageLatency(us), 2333.333
+[READ], MinLatency(us), 500
+[READ], MaxLatency(us), 5000
+[READ], 95thPercentileLatency(us), 5000
+[READ], 99thPercentileLatency(us), 5000
+[READ], 0, 1
+[READ], 1, 1
+[READ], >2, 1
+"
+        );
+
+        let workload = WorkloadBuilder::default()
+            .measurement_type(MeasurementType::HdrHistogram)
+            .build()
+            .unwrap();
+        let mut out = vec![];
+        write_histogram_report(Operation::ReadModifyWrite, &histogram, &workload, &mut out)
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("[READ-MODIFY-WRITE], Operations, 3\n"));
+        assert_eq!(out.lines().count(), 6);
+    }
+}
@@ -0,0 +1,104 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use crate::TimeseriesConfig;
+
+/// Average latency over consecutive windows of the configured granularity
+pub struct Timeseries {
+    start: Instant,
+    granularity: Duration,
+    /// Sample count and total latency of each window with samples
+    windows: BTreeMap<u64, (u64, Duration)>,
+}
+
+impl Timeseries {
+    pub fn new(config: TimeseriesConfig) -> Self {
+        Self::with_start(config, Instant::now())
+    }
+
+    /// Measure windows relative to `start` instead of the time of creation
+    ///
+    /// A zero granularity, which [`crate::Workload::validate`] rejects, is
+    /// treated as one nanosecond.
+    pub fn with_start(config: TimeseriesConfig, start: Instant) -> Self {
+        Self {
+            start,
+            granularity: config.granularity.max(Duration::from_nanos(1)),
+            windows: BTreeMap::new(),
+        }
+    }
+
+    /// Record a `latency` of an operation completed `at`
+    pub fn record(&mut self, at: Instant, latency: Duration) {
+        let offset = at.saturating_duration_since(self.start);
+        let window = (offset.as_nanos() / self.granularity.as_nanos()) as u64;
+        let (count, total) = self.windows.entry(window).or_default();
+        *count += 1;
+        *total += latency;
+    }
+
+    /// Offset from the start and average latency in microseconds of each
+    /// window with samples, in chronological order
+    pub fn windows(&self) -> Vec<(Duration, f64)> {
+        self.windows
+            .iter()
+            .map(|(window, (count, total))| {
+                // in nanoseconds, as the window index may not fit in a u32
+                let offset = self.granularity.as_nanos() * *window as u128;
+                (
+                    Duration::new(
+                        (offset / 1_000_000_000) as u64,
+                        (offset % 1_000_000_000) as u32,
+                    ),
+                    total.as_secs_f64() * 1e6 / *count as f64,
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_windows() {
+        let start = Instant::now();
+        let mut timeseries = Timeseries::with_start(
+            TimeseriesConfig {
+                granularity: Duration::from_millis(1000),
+            },
+            start,
+        );
+        timeseries.record(start, Duration::from_micros(100));
+        timeseries.record(
+            start + Duration::from_millis(999),
+            Duration::from_micros(300),
+        );
+        timeseries.record(
+            start + Duration::from_millis(2500),
+            Duration::from_micros(50),
+        );
+        assert_eq!(
+            timeseries.windows(),
+            vec![(Duration::ZERO, 200.), (Duration::from_secs(2), 50.),]
+        );
+    }
+
+    #[test]
+    fn test_fine_granularity() {
+        let start = Instant::now();
+        let mut timeseries = Timeseries::with_start(
+            TimeseriesConfig {
+                granularity: Duration::ZERO,
+            },
+            start,
+        );
+        // window 5_000_000_000 of one nanosecond does not fit in a u32
+        let at = Duration::from_secs(5);
+        timeseries.record(start + at, Duration::from_micros(10));
+        assert_eq!(timeseries.windows(), vec![(at, 10.)]);
+    }
+}
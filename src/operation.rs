@@ -0,0 +1,142 @@
+use std::{fmt, str::FromStr};
+
+use rand::rngs::SmallRng;
+
+use crate::{
+    generator::{DiscreteGenerator, Generator, WeightPair},
+    ParseVariantError, Workload,
+};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Operation {
+    Read,
+    Update,
+    Insert,
+    Scan,
+    ReadModifyWrite,
+    Delete,
+}
+
+impl Operation {
+    /// Label of the operation in YCSB's output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operation::Read => "READ",
+            Operation::Update => "UPDATE",
+            Operation::Insert => "INSERT",
+            Operation::Scan => "SCAN",
+            Operation::ReadModifyWrite => "READ-MODIFY-WRITE",
+            Operation::Delete => "DELETE",
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Operation {
+    type Err = ParseVariantError;
+
+    /// Parse the label of the operation in YCSB's output, e.g. `READ`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "READ" => Ok(Operation::Read),
+            "UPDATE" => Ok(Operation::Update),
+            "INSERT" => Ok(Operation::Insert),
+            "SCAN" => Ok(Operation::Scan),
+            "READ-MODIFY-WRITE" => Ok(Operation::ReadModifyWrite),
+            "DELETE" => Ok(Operation::Delete),
+            _ => Err(ParseVariantError {
+                kind: "operation",
+                value: s.to_owned(),
+            }),
+        }
+    }
+}
+
+/// Chooses the next operation weighted by the operation proportions
+/// of a workload
+pub struct OperationChooser {
+    generator: DiscreteGenerator<Operation>,
+}
+
+impl OperationChooser {
+    /// # Panics
+    /// If all operation proportions of `workload` are zero
+    pub fn new(workload: &Workload) -> Self {
+        let pairs: Vec<_> = [
+            (workload.read_proportion, Operation::Read),
+            (workload.update_proportion, Operation::Update),
+            (workload.insert_proportion, Operation::Insert),
+            (workload.scan_proportion, Operation::Scan),
+            (
+                workload.read_modify_write_proportion,
+                Operation::ReadModifyWrite,
+            ),
+            (workload.delete_proportion, Operation::Delete),
+        ]
+        .into_iter()
+        .filter(|(weight, _)| *weight > 0.0)
+        .map(|(weight, operation)| WeightPair::new(weight, operation))
+        .collect();
+        assert!(!pairs.is_empty(), "no operation has a positive proportion");
+        Self {
+            generator: DiscreteGenerator::new(pairs),
+        }
+    }
+
+    pub fn next_operation(&self, rng: &mut SmallRng) -> Operation {
+        self.generator.next_value(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WorkloadBuilder;
+    use rand::SeedableRng;
+    use std::collections::HashMap;
+
+    #[test]
+    fn follows_proportions() {
+        let workload = WorkloadBuilder::default()
+            .read_proportion(0.5)
+            .update_proportion(0.3)
+            .insert_proportion(0.1)
+            .scan_proportion(0.05)
+            .read_modify_write_proportion(0.03)
+            .delete_proportion(0.02)
+            .build()
+            .unwrap();
+        let chooser = OperationChooser::new(&workload);
+        let mut rng = SmallRng::seed_from_u64(0);
+        let draws = 1_000_000;
+        let mut counts = HashMap::new();
+        for _ in 0..draws {
+            *counts.entry(chooser.next_operation(&mut rng)).or_insert(0) += 1;
+        }
+        for (operation, proportion) in [
+            (Operation::Read, 0.5),
+            (Operation::Update, 0.3),
+            (Operation::Insert, 0.1),
+            (Operation::Scan, 0.05),
+            (Operation::ReadModifyWrite, 0.03),
+            (Operation::Delete, 0.02),
+        ] {
+            let observed = counts[&operation] as f64 / draws as f64;
+            assert!((observed - proportion).abs() < 0.005, "{}", operation);
+        }
+    }
+
+    #[test]
+    fn skips_zero_proportions() {
+        let chooser = OperationChooser::new(&Workload::c(1000, 1000));
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            assert_eq!(chooser.next_operation(&mut rng), Operation::Read);
+        }
+    }
+}
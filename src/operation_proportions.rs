@@ -0,0 +1,381 @@
+use crate::{Workload, WorkloadBuilder, WorkloadError, PROPORTION_EPSILON};
+
+/// The share of each kind of operation in the run phase of a workload
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Default)]
+pub struct OperationProportions {
+    pub read: f64,
+    pub update: f64,
+    pub insert: f64,
+    pub scan: f64,
+    pub read_modify_write: f64,
+    pub delete: f64,
+}
+
+/// The number of operations of each kind in the run phase of a workload
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct OperationCounts {
+    pub reads: u64,
+    pub updates: u64,
+    pub inserts: u64,
+    pub scans: u64,
+    pub read_modify_writes: u64,
+    pub deletes: u64,
+}
+
+impl OperationProportions {
+    /// Each proportion along with its property name
+    pub(crate) fn named(&self) -> [(&'static str, f64); 6] {
+        [
+            ("readproportion", self.read),
+            ("updateproportion", self.update),
+            ("insertproportion", self.insert),
+            ("readmodifywriteproportion", self.read_modify_write),
+            ("scanproportion", self.scan),
+            ("deleteproportion", self.delete),
+        ]
+    }
+
+    pub fn sum(&self) -> f64 {
+        self.named().iter().map(|(_, value)| value).sum()
+    }
+
+    /// Check that every proportion is within [0.0, 1.0] and that they sum
+    /// to 1.0
+    pub fn validate(&self) -> Result<(), WorkloadError> {
+        for (field, value) in self.named() {
+            if !value.is_finite() {
+                return Err(WorkloadError::NotFinite { field });
+            }
+            if !(0.0..=1.0).contains(&value) {
+                return Err(WorkloadError::InvalidProportion { field, value });
+            }
+        }
+        let sum = self.sum();
+        if (sum - 1.0).abs() > PROPORTION_EPSILON {
+            return Err(WorkloadError::ProportionsOutOfRange { sum });
+        }
+        Ok(())
+    }
+}
+
+impl From<&Workload> for OperationProportions {
+    fn from(workload: &Workload) -> Self {
+        Self {
+            read: workload.read_proportion,
+            update: workload.update_proportion,
+            insert: workload.insert_proportion,
+            scan: workload.scan_proportion,
+            read_modify_write: workload.read_modify_write_proportion,
+            delete: workload.delete_proportion,
+        }
+    }
+}
+
+impl Workload {
+    /// A copy of the workload with its operation proportions replaced
+    pub fn with_proportions(&self, proportions: OperationProportions) -> Workload {
+        let mut workload = self.clone();
+        workload.read_proportion = proportions.read;
+        workload.update_proportion = proportions.update;
+        workload.insert_proportion = proportions.insert;
+        workload.scan_proportion = proportions.scan;
+        workload.read_modify_write_proportion = proportions.read_modify_write;
+        workload.delete_proportion = proportions.delete;
+        workload
+    }
+
+    /// The combined proportion of operations that modify the table:
+    /// updates, inserts, read-modify-writes and deletes
+    fn write_proportion(&self) -> f64 {
+        self.update_proportion
+            + self.insert_proportion
+            + self.read_modify_write_proportion
+            + self.delete_proportion
+    }
+
+    /// Whether the run phase never modifies the table
+    pub fn is_read_only(&self) -> bool {
+        self.write_proportion() == 0.0
+    }
+
+    /// Whether more than half of the run phase's operations modify the table
+    pub fn is_write_heavy(&self) -> bool {
+        self.write_proportion() > 0.5
+    }
+
+    /// A workload of reads and updates in the ratio `read:write`, e.g.
+    /// `90:10`
+    ///
+    /// Fails with [`WorkloadError::ProportionsOutOfRange`] if both `read`
+    /// and `write` are zero.
+    pub fn from_read_write_ratio(
+        read: u32,
+        write: u32,
+        record_count: u64,
+        operation_count: u64,
+    ) -> Result<Workload, WorkloadError> {
+        let total = read as u64 + write as u64;
+        if total == 0 {
+            return Err(WorkloadError::ProportionsOutOfRange { sum: 0.0 });
+        }
+        Ok(WorkloadBuilder::default()
+            .record_count(record_count)
+            .operation_count(operation_count)
+            .proportions(OperationProportions {
+                read: read as f64 / total as f64,
+                update: write as f64 / total as f64,
+                ..Default::default()
+            })
+            .build()?)
+    }
+
+    /// A copy of the workload with the operation proportions rescaled to
+    /// sum to 1.0, keeping their ratios
+    ///
+    /// Fails with [`WorkloadError::NotFinite`] or
+    /// [`WorkloadError::InvalidProportion`] if a proportion is not a finite
+    /// non-negative number, and with [`WorkloadError::ProportionsOutOfRange`]
+    /// if the proportions sum to zero, as there is no ratio to keep.
+    pub fn normalized_proportions(&self) -> Result<Workload, WorkloadError> {
+        for (field, value) in OperationProportions::from(self).named() {
+            if !value.is_finite() {
+                return Err(WorkloadError::NotFinite { field });
+            }
+            if value < 0.0 {
+                return Err(WorkloadError::InvalidProportion { field, value });
+            }
+        }
+        let sum = self.read_proportion + self.scan_proportion + self.write_proportion();
+        if !(sum.is_finite() && sum > 0.0) {
+            return Err(WorkloadError::ProportionsOutOfRange { sum });
+        }
+        let mut normalized = self.clone();
+        normalized.read_proportion /= sum;
+        normalized.update_proportion /= sum;
+        normalized.insert_proportion /= sum;
+        normalized.scan_proportion /= sum;
+        normalized.read_modify_write_proportion /= sum;
+        normalized.delete_proportion /= sum;
+        Ok(normalized)
+    }
+
+    /// A copy of the workload with each operation proportion clamped to
+    /// [0.0, 1.0], and shrunk by the same factor if they then sum to more
+    /// than 1.0, to accept slightly off configurations instead of failing
+    /// [`Workload::validate`]
+    ///
+    /// A NaN proportion becomes 0.0. Proportions summing to less than 1.0
+    /// are kept, see [`Workload::normalized_proportions`] to scale them up.
+    pub fn clamped(&self) -> Workload {
+        let clamp = |value: f64| {
+            if value.is_nan() {
+                0.0
+            } else {
+                value.clamp(0.0, 1.0)
+            }
+        };
+        let proportions = OperationProportions::from(self);
+        let mut clamped = OperationProportions {
+            read: clamp(proportions.read),
+            update: clamp(proportions.update),
+            insert: clamp(proportions.insert),
+            scan: clamp(proportions.scan),
+            read_modify_write: clamp(proportions.read_modify_write),
+            delete: clamp(proportions.delete),
+        };
+        let sum = clamped.sum();
+        if sum > 1.0 {
+            clamped.read /= sum;
+            clamped.update /= sum;
+            clamped.insert /= sum;
+            clamped.scan /= sum;
+            clamped.read_modify_write /= sum;
+            clamped.delete /= sum;
+        }
+        self.with_proportions(clamped)
+    }
+
+    /// The number of operations of each kind the run phase is expected to
+    /// perform, each proportion of `operation_count` rounded to the
+    /// nearest integer
+    pub fn expected_operation_counts(&self) -> OperationCounts {
+        let count = |proportion: f64| (proportion * self.operation_count as f64).round() as u64;
+        OperationCounts {
+            reads: count(self.read_proportion),
+            updates: count(self.update_proportion),
+            inserts: count(self.insert_proportion),
+            scans: count(self.scan_proportion),
+            read_modify_writes: count(self.read_modify_write_proportion),
+            deletes: count(self.delete_proportion),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StandardWorkload;
+
+    #[test]
+    fn validate() {
+        assert!(OperationProportions {
+            read: 0.5,
+            scan: 0.5,
+            ..Default::default()
+        }
+        .validate()
+        .is_ok());
+        assert!(matches!(
+            OperationProportions::default().validate(),
+            Err(WorkloadError::ProportionsOutOfRange { sum }) if sum == 0.0
+        ));
+        assert!(matches!(
+            OperationProportions {
+                read: 1.5,
+                update: -0.5,
+                ..Default::default()
+            }
+            .validate(),
+            Err(WorkloadError::InvalidProportion {
+                field: "readproportion",
+                ..
+            })
+        ));
+        assert!(matches!(
+            OperationProportions {
+                delete: f64::NAN,
+                ..Default::default()
+            }
+            .validate(),
+            Err(WorkloadError::NotFinite {
+                field: "deleteproportion"
+            })
+        ));
+    }
+
+    #[test]
+    fn workload_conversion() {
+        let e = Workload::e(1000, 1000);
+        let proportions = OperationProportions::from(&e);
+        assert_eq!(proportions, StandardWorkload::E.proportions());
+        let c = Workload::c(1000, 1000);
+        assert_eq!(
+            OperationProportions::from(&c.with_proportions(proportions)),
+            proportions
+        );
+        assert_eq!(
+            e.with_proportions(StandardWorkload::C.proportions())
+                .read_proportion,
+            1.0
+        );
+    }
+
+    #[test]
+    fn read_only_and_write_heavy() {
+        assert!(Workload::c(1000, 1000).is_read_only());
+        assert!(!Workload::b(1000, 1000).is_read_only());
+        assert!(!Workload::e(1000, 1000).is_read_only());
+        assert!(!Workload::a(1000, 1000).is_write_heavy());
+        assert!(!Workload::e(1000, 1000).is_write_heavy());
+        assert!(Workload::load_only(1000).is_write_heavy());
+    }
+
+    #[test]
+    fn from_read_write_ratio() {
+        let config = Workload::from_read_write_ratio(90, 10, 1000, 2000).unwrap();
+        assert_eq!(config.read_proportion, 0.9);
+        assert_eq!(config.update_proportion, 0.1);
+        assert_eq!(config.record_count, 1000);
+        assert_eq!(config.operation_count, 2000);
+        assert!(config.validate().is_ok());
+        let config = Workload::from_read_write_ratio(0, u32::MAX, 1, 1).unwrap();
+        assert!(config.is_write_heavy());
+        assert!(matches!(
+            Workload::from_read_write_ratio(0, 0, 1000, 1000),
+            Err(WorkloadError::ProportionsOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn clamped() {
+        let a = Workload::a(1000, 1000);
+        assert_eq!(a.clamped(), a);
+
+        let mut config = a.clone();
+        config.read_proportion = 0.51;
+        config.update_proportion = 0.51;
+        let clamped = config.clamped();
+        assert!(clamped.approx_eq(&a, 1e-12));
+        assert!(clamped.validate().is_ok());
+
+        config.read_proportion = 1.5;
+        config.update_proportion = -0.5;
+        config.delete_proportion = f64::NAN;
+        let clamped = config.clamped();
+        assert_eq!(clamped.read_proportion(), 1.0);
+        assert_eq!(clamped.update_proportion(), 0.0);
+        assert_eq!(clamped.delete_proportion(), 0.0);
+        assert!(clamped.validate().is_ok());
+
+        // proportions summing to less than 1.0 are only clamped
+        config.read_proportion = 0.25;
+        assert_eq!(config.clamped().read_proportion(), 0.25);
+        assert!(config.clamped().validate().is_err());
+    }
+
+    #[test]
+    fn normalized_proportions() {
+        let mut config = Workload::a(1000, 1000);
+        config.read_proportion = 0.495;
+        config.update_proportion = 0.495;
+        assert!(config.validate().is_err());
+        let normalized = config.normalized_proportions().unwrap();
+        assert!(normalized.approx_eq(&Workload::a(1000, 1000), 1e-12));
+        assert!(normalized.validate().is_ok());
+
+        config.read_proportion = 0.;
+        config.update_proportion = 0.;
+        assert!(matches!(
+            config.normalized_proportions(),
+            Err(WorkloadError::ProportionsOutOfRange { sum }) if sum == 0.0
+        ));
+
+        // sums to 1.0, but is no valid ratio
+        config.read_proportion = 1.5;
+        config.update_proportion = -0.5;
+        assert!(matches!(
+            config.normalized_proportions(),
+            Err(WorkloadError::InvalidProportion {
+                field: "updateproportion",
+                value
+            }) if value == -0.5
+        ));
+        config.update_proportion = f64::INFINITY;
+        assert!(matches!(
+            config.normalized_proportions(),
+            Err(WorkloadError::NotFinite {
+                field: "updateproportion"
+            })
+        ));
+    }
+
+    #[test]
+    fn expected_operation_counts() {
+        assert_eq!(
+            Workload::default().expected_operation_counts(),
+            OperationCounts {
+                reads: 2_850_000,
+                updates: 150_000,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            Workload::e(1000, 333).expected_operation_counts(),
+            OperationCounts {
+                scans: 316,
+                inserts: 17,
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,281 @@
+use std::time::Duration;
+
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde_with::{As, DurationSeconds};
+
+use crate::{
+    Distribution, ExponentialConfig, FieldSpec, HdrHistogramConfig, HistogramConfig, InsertOrder,
+    MeasurementType, ScanConfig, SloConfig, TimeseriesConfig, Workload, ZipfianConfig,
+};
+
+/// A set of overrides for the fields of a [`Workload`], where `None`
+/// leaves the corresponding field untouched
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PartialWorkload {
+    pub workload: Option<String>,
+    #[cfg_attr(feature = "serde", serde(rename = "recordcount"))]
+    pub record_count: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(rename = "operationcount"))]
+    pub operation_count: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(rename = "threadcount"))]
+    pub thread_count: Option<u64>,
+    pub target: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(rename = "insertcount"))]
+    pub insert_count: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(rename = "insertstart"))]
+    pub insert_start: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(rename = "fieldcount"))]
+    pub field_count: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(rename = "fieldnameprefix"))]
+    pub field_name_prefix: Option<String>,
+    #[cfg_attr(feature = "serde", serde(rename = "fieldlength"))]
+    pub field_length: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(rename = "readallfields"))]
+    pub read_all_fields: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(rename = "writeallfields"))]
+    pub write_all_fields: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(rename = "dataintegrity"))]
+    pub data_integrity: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(rename = "fieldlengthdistribution"))]
+    pub field_length_distribution: Option<Distribution>,
+    #[cfg_attr(feature = "serde", serde(rename = "readproportion"))]
+    pub read_proportion: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(rename = "updateproportion"))]
+    pub update_proportion: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(rename = "insertproportion"))]
+    pub insert_proportion: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(rename = "readmodifywriteproportion"))]
+    pub read_modify_write_proportion: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(rename = "scanproportion"))]
+    pub scan_proportion: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(rename = "deleteproportion"))]
+    pub delete_proportion: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(rename = "maxscanlength"))]
+    pub max_scan_length: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(rename = "scanlengthdistribution"))]
+    pub scan_length_distribution: Option<Distribution>,
+    #[cfg_attr(feature = "serde", serde(rename = "insertorder"))]
+    pub insert_order: Option<InsertOrder>,
+    #[cfg_attr(feature = "serde", serde(rename = "zeropadding"))]
+    pub zero_padding: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(rename = "requestdistribution"))]
+    pub request_distribution: Option<Distribution>,
+    #[cfg_attr(feature = "serde", serde(rename = "hotspotdatafraction"))]
+    pub hotspot_data_fraction: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(rename = "hotspotopnfraction"))]
+    pub hotspot_operation_fraction: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(with = "As::<Option<DurationSeconds<u64>>>"))]
+    #[cfg_attr(feature = "serde", serde(rename = "maxexecutiontime"))]
+    pub max_execution_time: Option<Duration>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "core_workload_insertion_retry_limit")
+    )]
+    pub insertion_retry_limit: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(with = "As::<Option<DurationSeconds<u64>>>"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "core_workload_insertion_retry_interval")
+    )]
+    pub insertion_retry_interval: Option<Duration>,
+    #[cfg_attr(feature = "serde", serde(with = "As::<Option<DurationSeconds<u64>>>"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "statusinterval", alias = "status.interval")
+    )]
+    pub status_interval: Option<Duration>,
+    pub table: Option<String>,
+    #[cfg_attr(feature = "serde", serde(rename = "columnfamily"))]
+    pub column_family: Option<String>,
+    #[cfg_attr(feature = "serde", serde(rename = "measurementtype"))]
+    pub measurement_type: Option<MeasurementType>,
+    #[cfg_attr(feature = "serde", serde(rename = "exportfile"))]
+    pub export_file: Option<String>,
+    #[cfg_attr(feature = "serde", serde(with = "As::<Option<DurationSeconds<u64>>>"))]
+    #[cfg_attr(feature = "serde", serde(rename = "exportmeasurementsinterval"))]
+    pub export_measurements_interval: Option<Duration>,
+    pub seed: Option<u64>,
+    pub histogram: Option<HistogramConfig>,
+    pub timeseries: Option<TimeseriesConfig>,
+    pub exponential: Option<ExponentialConfig>,
+    pub zipfian: Option<ZipfianConfig>,
+    pub scan: Option<ScanConfig>,
+    pub hdrhistogram: Option<HdrHistogramConfig>,
+    pub slo: Option<SloConfig>,
+    #[cfg_attr(feature = "serde", serde(rename = "field"))]
+    pub field_specs: Option<Vec<FieldSpec>>,
+}
+
+impl Workload {
+    /// A copy of the workload with every field set in `overrides` replaced
+    pub fn merge(&self, overrides: &PartialWorkload) -> Workload {
+        let mut merged = self.clone();
+        if let Some(v) = &overrides.workload {
+            merged.workload = v.clone();
+        }
+        if let Some(v) = overrides.record_count {
+            merged.record_count = v;
+        }
+        if let Some(v) = overrides.operation_count {
+            merged.operation_count = v;
+        }
+        if let Some(v) = overrides.thread_count {
+            merged.thread_count = v;
+        }
+        if let Some(v) = overrides.target {
+            merged.target = v;
+        }
+        if let Some(v) = overrides.insert_count {
+            merged.insert_count = v;
+        }
+        if let Some(v) = overrides.insert_start {
+            merged.insert_start = v;
+        }
+        if let Some(v) = overrides.field_count {
+            merged.field_count = v;
+        }
+        if let Some(v) = &overrides.field_name_prefix {
+            merged.field_name_prefix = v.clone();
+        }
+        if let Some(v) = overrides.field_length {
+            merged.field_length = v;
+        }
+        if let Some(v) = overrides.read_all_fields {
+            merged.read_all_fields = v;
+        }
+        if let Some(v) = overrides.write_all_fields {
+            merged.write_all_fields = v;
+        }
+        if let Some(v) = overrides.data_integrity {
+            merged.data_integrity = v;
+        }
+        if let Some(v) = overrides.field_length_distribution {
+            merged.field_length_distribution = v;
+        }
+        if let Some(v) = overrides.read_proportion {
+            merged.read_proportion = v;
+        }
+        if let Some(v) = overrides.update_proportion {
+            merged.update_proportion = v;
+        }
+        if let Some(v) = overrides.insert_proportion {
+            merged.insert_proportion = v;
+        }
+        if let Some(v) = overrides.read_modify_write_proportion {
+            merged.read_modify_write_proportion = v;
+        }
+        if let Some(v) = overrides.scan_proportion {
+            merged.scan_proportion = v;
+        }
+        if let Some(v) = overrides.delete_proportion {
+            merged.delete_proportion = v;
+        }
+        if let Some(v) = overrides.max_scan_length {
+            merged.max_scan_length = v;
+        }
+        if let Some(v) = overrides.scan_length_distribution {
+            merged.scan_length_distribution = v;
+        }
+        if let Some(v) = overrides.insert_order {
+            merged.insert_order = v;
+        }
+        if let Some(v) = overrides.zero_padding {
+            merged.zero_padding = v;
+        }
+        if let Some(v) = overrides.request_distribution {
+            merged.request_distribution = v;
+        }
+        if let Some(v) = overrides.hotspot_data_fraction {
+            merged.hotspot_data_fraction = v;
+        }
+        if let Some(v) = overrides.hotspot_operation_fraction {
+            merged.hotspot_operation_fraction = v;
+        }
+        if let Some(v) = overrides.max_execution_time {
+            merged.max_execution_time = v;
+        }
+        if let Some(v) = overrides.insertion_retry_limit {
+            merged.insertion_retry_limit = v;
+        }
+        if let Some(v) = overrides.insertion_retry_interval {
+            merged.insertion_retry_interval = v;
+        }
+        if let Some(v) = overrides.status_interval {
+            merged.status_interval = v;
+        }
+        if let Some(v) = &overrides.table {
+            merged.table = v.clone();
+        }
+        if let Some(v) = &overrides.column_family {
+            merged.column_family = v.clone();
+        }
+        if let Some(v) = overrides.measurement_type {
+            merged.measurement_type = v;
+        }
+        if let Some(v) = &overrides.export_file {
+            merged.export_file = Some(v.clone());
+        }
+        if let Some(v) = overrides.export_measurements_interval {
+            merged.export_measurements_interval = v;
+        }
+        if let Some(v) = overrides.seed {
+            merged.seed = Some(v);
+        }
+        if let Some(v) = &overrides.histogram {
+            merged.histogram = v.clone();
+        }
+        if let Some(v) = overrides.timeseries {
+            merged.timeseries = v;
+        }
+        if let Some(v) = overrides.exponential {
+            merged.exponential = v;
+        }
+        if let Some(v) = overrides.zipfian {
+            merged.zipfian = v;
+        }
+        if let Some(v) = overrides.scan {
+            merged.scan = v;
+        }
+        if let Some(v) = &overrides.hdrhistogram {
+            merged.hdrhistogram = v.clone();
+        }
+        if let Some(v) = overrides.slo {
+            merged.slo = v;
+        }
+        if let Some(v) = &overrides.field_specs {
+            merged.field_specs = v.clone();
+        }
+        merged
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_overrides() {
+        let overrides: PartialWorkload = toml::from_str(
+            r#"
+readproportion = 0.8
+updateproportion = 0.2
+maxexecutiontime = 60
+seed = 7
+"#,
+        )
+        .unwrap();
+        let base = Workload::a(1000, 2000);
+        let merged = base.merge(&overrides);
+        assert_eq!(merged.read_proportion, 0.8);
+        assert_eq!(merged.update_proportion, 0.2);
+        assert_eq!(merged.max_execution_time, Duration::from_secs(60));
+        assert_eq!(merged.seed, Some(7));
+        assert_eq!(merged.record_count, 1000);
+        assert_eq!(merged.operation_count, 2000);
+        assert_eq!(base.merge(&PartialWorkload::default()), base);
+    }
+}
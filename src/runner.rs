@@ -0,0 +1,848 @@
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    io::{self, Write},
+    pin::pin,
+    task::{Context, Poll, Waker},
+    time::{Duration, Instant},
+};
+
+use rand::rngs::SmallRng;
+
+use crate::{
+    generator::{Generator, UniformLongGenerator, ZipfianGenerator},
+    measurement::Histogram,
+    Distribution, FieldLengthGenerator, HistogramConfig, Operation, RngSalt, SloConfig, Workload,
+    WorkloadError,
+};
+
+/// The values written to a record, as `(field name, value)` pairs
+pub type Values = [(String, Vec<u8>)];
+
+/// A database backend a workload can be run against
+///
+/// Implementations may simply write `async fn` for each method.
+pub trait Database {
+    type Error;
+
+    /// Read `fields` of the record `key`
+    fn read(
+        &mut self,
+        table: &str,
+        key: &str,
+        fields: &[String],
+    ) -> impl Future<Output = Result<(), Self::Error>>;
+    /// Overwrite the given fields of the record `key`
+    fn update(
+        &mut self,
+        table: &str,
+        key: &str,
+        values: &Values,
+    ) -> impl Future<Output = Result<(), Self::Error>>;
+    /// Insert the record `key`
+    fn insert(
+        &mut self,
+        table: &str,
+        key: &str,
+        values: &Values,
+    ) -> impl Future<Output = Result<(), Self::Error>>;
+    /// Read `fields` of `count` records in key order starting at `start_key`
+    fn scan(
+        &mut self,
+        table: &str,
+        start_key: &str,
+        count: u64,
+        fields: &[String],
+    ) -> impl Future<Output = Result<(), Self::Error>>;
+    /// Delete the record `key`
+    fn delete(&mut self, table: &str, key: &str) -> impl Future<Output = Result<(), Self::Error>>;
+}
+
+/// The optional operations a database backend supports, checked by
+/// [`Workload::validate_for`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DatabaseCapabilities {
+    pub supports_scan: bool,
+    pub supports_delete: bool,
+}
+
+impl Default for DatabaseCapabilities {
+    /// A database supporting every operation
+    fn default() -> Self {
+        Self {
+            supports_scan: true,
+            supports_delete: true,
+        }
+    }
+}
+
+impl Workload {
+    /// Like [`Workload::validate`], but also fail with
+    /// [`WorkloadError::UnsupportedOperation`] if the workload performs an
+    /// operation the database does not support
+    pub fn validate_for(&self, capabilities: DatabaseCapabilities) -> Result<(), WorkloadError> {
+        self.validate()?;
+        for (operation, proportion, supported) in [
+            (
+                Operation::Scan,
+                self.scan_proportion,
+                capabilities.supports_scan,
+            ),
+            (
+                Operation::Delete,
+                self.delete_proportion,
+                capabilities.supports_delete,
+            ),
+        ] {
+            if proportion > 0.0 && !supported {
+                return Err(WorkloadError::UnsupportedOperation {
+                    operation,
+                    proportion,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A blocking database backend a workload can be run against, mirroring
+/// [`Database`]
+pub trait SyncDatabase {
+    type Error;
+
+    /// Read `fields` of the record `key`
+    fn read(&mut self, table: &str, key: &str, fields: &[String]) -> Result<(), Self::Error>;
+    /// Overwrite the given fields of the record `key`
+    fn update(&mut self, table: &str, key: &str, values: &Values) -> Result<(), Self::Error>;
+    /// Insert the record `key`
+    fn insert(&mut self, table: &str, key: &str, values: &Values) -> Result<(), Self::Error>;
+    /// Read `fields` of `count` records in key order starting at `start_key`
+    fn scan(
+        &mut self,
+        table: &str,
+        start_key: &str,
+        count: u64,
+        fields: &[String],
+    ) -> Result<(), Self::Error>;
+    /// Delete the record `key`
+    fn delete(&mut self, table: &str, key: &str) -> Result<(), Self::Error>;
+}
+
+/// Exposes a [`SyncDatabase`] as a [`Database`] whose futures complete
+/// on their first poll
+struct Blocking<'a, D>(&'a mut D);
+
+impl<D: SyncDatabase> Database for Blocking<'_, D> {
+    type Error = D::Error;
+
+    async fn read(&mut self, table: &str, key: &str, fields: &[String]) -> Result<(), D::Error> {
+        self.0.read(table, key, fields)
+    }
+    async fn update(&mut self, table: &str, key: &str, values: &Values) -> Result<(), D::Error> {
+        self.0.update(table, key, values)
+    }
+    async fn insert(&mut self, table: &str, key: &str, values: &Values) -> Result<(), D::Error> {
+        self.0.insert(table, key, values)
+    }
+    async fn scan(
+        &mut self,
+        table: &str,
+        start_key: &str,
+        count: u64,
+        fields: &[String],
+    ) -> Result<(), D::Error> {
+        self.0.scan(table, start_key, count, fields)
+    }
+    async fn delete(&mut self, table: &str, key: &str) -> Result<(), D::Error> {
+        self.0.delete(table, key)
+    }
+}
+
+/// The operations performed while running a workload and their latencies
+#[derive(Debug, Clone, Default)]
+pub struct RunReport {
+    /// The number of operations attempted, failed ones included
+    pub total_operations: u64,
+    /// The number of operations of each kind attempted
+    pub operations: BTreeMap<Operation, u64>,
+    /// Latencies of the successful operations of each kind
+    pub histograms: BTreeMap<Operation, Histogram>,
+    /// The number of failed operations of each kind
+    pub failures: BTreeMap<Operation, u64>,
+    /// Wall-clock time of the whole run
+    pub elapsed: Duration,
+}
+
+impl RunReport {
+    /// An empty report, to be filled by [`RunReport::record`] by drivers
+    /// other than [`run_workload`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attempted operations per second of wall-clock time, 0.0 if no time
+    /// has elapsed
+    pub fn throughput_ops_per_sec(&self) -> f64 {
+        if self.elapsed.is_zero() {
+            return 0.0;
+        }
+        self.total_operations as f64 / self.elapsed.as_secs_f64()
+    }
+
+    /// The `p`th percentile latency of the successful operations of kind
+    /// `operation`, `None` if none succeeded
+    pub fn latency_percentile(&self, operation: Operation, p: f64) -> Option<Duration> {
+        self.histograms
+            .get(&operation)
+            .map(|histogram| histogram.percentile(p))
+    }
+
+    /// A description of each operation whose p99 latency exceeds its
+    /// objective in `slo`
+    pub fn slo_violations(&self, slo: &SloConfig) -> Vec<String> {
+        self.histograms
+            .iter()
+            .filter_map(|(&operation, histogram)| {
+                let target = slo.p99(operation)?;
+                let p99 = histogram.percentile(99.);
+                (p99 > target).then(|| {
+                    format!(
+                        "{} p99 latency {:?} exceeds the objective of {:?}",
+                        operation, p99, target
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Count an operation, recording its latency in a histogram built
+    /// from `config` if it succeeded
+    pub fn record(
+        &mut self,
+        config: &HistogramConfig,
+        operation: Operation,
+        latency: Duration,
+        ok: bool,
+    ) {
+        self.total_operations += 1;
+        *self.operations.entry(operation).or_default() += 1;
+        if ok {
+            self.histograms
+                .entry(operation)
+                .or_insert_with(|| Histogram::new(config.clone()))
+                .record(latency);
+        } else {
+            *self.failures.entry(operation).or_default() += 1;
+        }
+    }
+}
+
+/// A single operation of a run along with its arguments
+enum Request {
+    Read {
+        key: String,
+        fields: Vec<String>,
+    },
+    Update {
+        key: String,
+        values: Vec<(String, Vec<u8>)>,
+    },
+    Insert {
+        key: String,
+        values: Vec<(String, Vec<u8>)>,
+    },
+    Scan {
+        start_key: String,
+        count: u64,
+        fields: Vec<String>,
+    },
+    ReadModifyWrite {
+        key: String,
+        fields: Vec<String>,
+        values: Vec<(String, Vec<u8>)>,
+    },
+    Delete {
+        key: String,
+    },
+}
+
+impl Request {
+    fn operation(&self) -> Operation {
+        match self {
+            Request::Read { .. } => Operation::Read,
+            Request::Update { .. } => Operation::Update,
+            Request::Insert { .. } => Operation::Insert,
+            Request::Scan { .. } => Operation::Scan,
+            Request::ReadModifyWrite { .. } => Operation::ReadModifyWrite,
+            Request::Delete { .. } => Operation::Delete,
+        }
+    }
+
+    fn key(&self) -> &str {
+        match self {
+            Request::Read { key, .. }
+            | Request::Update { key, .. }
+            | Request::Insert { key, .. }
+            | Request::ReadModifyWrite { key, .. }
+            | Request::Delete { key } => key,
+            Request::Scan { start_key, .. } => start_key,
+        }
+    }
+}
+
+/// Turns the operation plan of a workload into requests, choosing the
+/// fields and scan lengths of each operation
+struct Requests<'a> {
+    workload: &'a Workload,
+    plan: Box<dyn Iterator<Item = (Operation, u64)>>,
+    rng: SmallRng,
+    field_names: Vec<String>,
+    field_lengths: Vec<FieldLengthGenerator>,
+    scan_length: Box<dyn Generator<u64> + Send>,
+}
+
+impl<'a> Requests<'a> {
+    /// # Panics
+    /// If `workload` does not pass [`Workload::validate`], e.g. it reads
+    /// from an empty table or has an unsupported length distribution
+    fn new(workload: &'a Workload) -> Self {
+        let max_scan_length = workload.max_scan_length.max(1);
+        let scan_length: Box<dyn Generator<u64> + Send> = match workload.scan_length_distribution {
+            Distribution::Uniform => Box::new(UniformLongGenerator::new(1, max_scan_length)),
+            Distribution::Zipfian => Box::new(ZipfianGenerator::from_range_const(
+                1,
+                max_scan_length,
+                workload.scan.zipfian_constant,
+            )),
+            distribution => panic!("unsupported scan length distribution {}", distribution),
+        };
+        Self {
+            workload,
+            plan: Box::new(workload.operation_plan()),
+            rng: workload.salted_rng(RngSalt::Requests),
+            field_names: workload.field_names(),
+            field_lengths: (0..workload.field_count)
+                .map(|index| FieldLengthGenerator::for_field(workload, index))
+                .collect(),
+            scan_length,
+        }
+    }
+
+    fn read_fields(&mut self) -> Vec<String> {
+        self.workload
+            .fields_to_read(&mut self.rng)
+            .into_iter()
+            .map(|i| self.field_names[i as usize].clone())
+            .collect()
+    }
+
+    fn write_values(&mut self, key: u64) -> Vec<(String, Vec<u8>)> {
+        let indices = self.workload.fields_to_write(&mut self.rng);
+        self.values(key, indices)
+    }
+
+    /// The values of fields `indices` of record `key`, each with a length
+    /// drawn from its field length distribution
+    fn values(&mut self, key: u64, indices: Vec<u64>) -> Vec<(String, Vec<u8>)> {
+        indices
+            .into_iter()
+            .map(|i| {
+                let length = self.field_lengths[i as usize].next_length();
+                let value = self
+                    .workload
+                    .deterministic_field_value_with_length(key, i, length);
+                (self.field_names[i as usize].clone(), value)
+            })
+            .collect()
+    }
+}
+
+impl Iterator for Requests<'_> {
+    type Item = Request;
+
+    fn next(&mut self) -> Option<Request> {
+        let (operation, keynum) = self.plan.next()?;
+        let key = self.workload.record_key(keynum);
+        let workload = self.workload;
+        Some(match operation {
+            Operation::Read => Request::Read {
+                key,
+                fields: self.read_fields(),
+            },
+            Operation::Update => Request::Update {
+                key,
+                values: self.write_values(keynum),
+            },
+            Operation::Insert => Request::Insert {
+                key,
+                values: self.values(keynum, (0..workload.field_count).collect()),
+            },
+            Operation::Scan => Request::Scan {
+                start_key: key,
+                count: self.scan_length.next_value(&mut self.rng),
+                fields: self.read_fields(),
+            },
+            Operation::ReadModifyWrite => Request::ReadModifyWrite {
+                key,
+                fields: self.read_fields(),
+                values: self.write_values(keynum),
+            },
+            Operation::Delete => Request::Delete { key },
+        })
+    }
+}
+
+impl Workload {
+    /// Write the run's requests as CSV with the columns
+    /// `sequence,operation,key,scanlength`, the scan length being empty for
+    /// anything but scans
+    ///
+    /// Fails if the workload does not pass [`Workload::validate`] or
+    /// `writer` fails.
+    pub fn write_operation_plan_csv<W: Write>(&self, writer: W) -> Result<(), WorkloadError> {
+        self.validate()?;
+        write_requests_csv(Requests::new(self), writer).map_err(WorkloadError::Write)
+    }
+}
+
+fn write_requests_csv<W: Write>(requests: Requests, mut writer: W) -> io::Result<()> {
+    writeln!(writer, "sequence,operation,key,scanlength")?;
+    for (sequence, request) in requests.enumerate() {
+        write!(
+            writer,
+            "{},{},{},",
+            sequence,
+            request.operation(),
+            request.key()
+        )?;
+        match request {
+            Request::Scan { count, .. } => writeln!(writer, "{}", count)?,
+            _ => writeln!(writer)?,
+        }
+    }
+    Ok(())
+}
+
+/// Run the transactions of `workload` against `db` one at a time,
+/// stopping early once `max_execution_time` (if set) has elapsed
+///
+/// # Panics
+/// If `workload` does not pass [`Workload::validate`], which rejects the
+/// workloads that cannot be planned, such as reads from an empty table;
+/// check it first, or use [`crate::WorkloadBuilder::build_validated`]
+pub async fn run_workload<D: Database>(workload: &Workload, db: &mut D) -> RunReport {
+    let table = workload.table.as_str();
+    let mut report = RunReport::new();
+    let start = Instant::now();
+    for request in Requests::new(workload) {
+        if let Some(limit) = workload.max_execution_time() {
+            if start.elapsed() >= limit {
+                break;
+            }
+        }
+        let operation = request.operation();
+        let begin = Instant::now();
+        let result = match &request {
+            Request::Read { key, fields } => db.read(table, key, fields).await,
+            Request::Update { key, values } => db.update(table, key, values).await,
+            Request::Insert { key, values } => db.insert(table, key, values).await,
+            Request::Scan {
+                start_key,
+                count,
+                fields,
+            } => db.scan(table, start_key, *count, fields).await,
+            Request::ReadModifyWrite {
+                key,
+                fields,
+                values,
+            } => match db.read(table, key, fields).await {
+                Ok(()) => db.update(table, key, values).await,
+                err => err,
+            },
+            Request::Delete { key } => db.delete(table, key).await,
+        };
+        report.record(
+            &workload.histogram,
+            operation,
+            begin.elapsed(),
+            result.is_ok(),
+        );
+    }
+    report.elapsed = start.elapsed();
+    report
+}
+
+/// Run the transactions of `workload` against the blocking `db`, see
+/// [`run_workload`]
+///
+/// # Panics
+/// If `workload` does not pass [`Workload::validate`]
+pub fn run_workload_sync<D: SyncDatabase>(workload: &Workload, db: &mut D) -> RunReport {
+    let mut db = Blocking(db);
+    let mut future = pin!(run_workload(workload, &mut db));
+    match future
+        .as_mut()
+        .poll(&mut Context::from_waker(Waker::noop()))
+    {
+        Poll::Ready(report) => report,
+        Poll::Pending => unreachable!("blocking database operations never suspend"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        FieldSpec, InsertKeyGenerator, InsertOrder, OperationProportions, StandardWorkload,
+        WorkloadBuilder,
+    };
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct CountingDatabase {
+        operations: BTreeMap<&'static str, u64>,
+    }
+
+    impl CountingDatabase {
+        fn count(&mut self, operation: &'static str) -> Result<(), ()> {
+            *self.operations.entry(operation).or_default() += 1;
+            Ok(())
+        }
+    }
+
+    impl Database for CountingDatabase {
+        type Error = ();
+
+        async fn read(&mut self, table: &str, key: &str, fields: &[String]) -> Result<(), ()> {
+            assert_eq!(table, "usertable");
+            assert!(key.starts_with("user"));
+            assert_eq!(fields.len(), 10);
+            self.count("read")
+        }
+        async fn update(&mut self, _: &str, _: &str, values: &Values) -> Result<(), ()> {
+            assert_eq!(values.len(), 1);
+            assert_eq!(values[0].1.len(), 100);
+            self.count("update")
+        }
+        async fn insert(&mut self, _: &str, _: &str, values: &Values) -> Result<(), ()> {
+            assert_eq!(values.len(), 10);
+            self.count("insert")
+        }
+        async fn scan(&mut self, _: &str, _: &str, count: u64, _: &[String]) -> Result<(), ()> {
+            assert!((1..=100).contains(&count));
+            self.count("scan")
+        }
+        async fn delete(&mut self, _: &str, _: &str) -> Result<(), ()> {
+            Err(())
+        }
+    }
+
+    #[test]
+    fn run_workload() {
+        let workload = Workload::a(100, 1000);
+        let mut db = CountingDatabase::default();
+        let report = block_on(super::run_workload(&workload, &mut db));
+        let reads = db.operations["read"];
+        let updates = db.operations["update"];
+        assert_eq!(reads + updates, 1000);
+        assert_eq!(report.histograms[&Operation::Read].count(), reads);
+        assert_eq!(report.histograms[&Operation::Update].count(), updates);
+        assert_eq!(report.operations[&Operation::Read], reads);
+        assert_eq!(report.total_operations, 1000);
+        assert!(report.failures.is_empty());
+
+        let mut db = CountingDatabase::default();
+        block_on(super::run_workload(&Workload::e(100, 1000), &mut db));
+        assert_eq!(db.operations["scan"] + db.operations["insert"], 1000);
+
+        let workload = WorkloadBuilder::default()
+            .record_count(100)
+            .operation_count(10)
+            .proportions(StandardWorkload::C.proportions())
+            .delete_proportion(1.0)
+            .read_proportion(0.0)
+            .build()
+            .unwrap();
+        let report = block_on(super::run_workload(
+            &workload,
+            &mut CountingDatabase::default(),
+        ));
+        assert_eq!(report.failures[&Operation::Delete], 10);
+        assert_eq!(report.operations[&Operation::Delete], 10);
+        assert!(report.histograms.is_empty());
+    }
+
+    struct Memory(BTreeMap<String, Vec<(String, Vec<u8>)>>);
+
+    impl SyncDatabase for Memory {
+        type Error = &'static str;
+
+        fn read(&mut self, _: &str, key: &str, _: &[String]) -> Result<(), Self::Error> {
+            self.0.get(key).map(|_| ()).ok_or("not found")
+        }
+        fn update(&mut self, _: &str, key: &str, values: &Values) -> Result<(), Self::Error> {
+            let record = self.0.get_mut(key).ok_or("not found")?;
+            for (field, value) in values {
+                let (_, old) = record.iter_mut().find(|(f, _)| f == field).unwrap();
+                *old = value.clone();
+            }
+            Ok(())
+        }
+        fn insert(&mut self, _: &str, key: &str, values: &Values) -> Result<(), Self::Error> {
+            self.0.insert(key.to_owned(), values.to_vec());
+            Ok(())
+        }
+        fn scan(&mut self, _: &str, _: &str, _: u64, _: &[String]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+        fn delete(&mut self, _: &str, key: &str) -> Result<(), Self::Error> {
+            self.0.remove(key).map(|_| ()).ok_or("not found")
+        }
+    }
+
+    /// A database holding the records the load phase of `workload` inserts
+    fn loaded(workload: &Workload) -> Memory {
+        let mut db = Memory(BTreeMap::new());
+        let keys = InsertKeyGenerator::from_workload(workload);
+        for (keynum, key) in keys.take(workload.record_count() as usize).enumerate() {
+            let values: Vec<_> = (0..workload.field_count())
+                .map(|i| {
+                    let field = format!("field{}", i);
+                    (field, workload.deterministic_field_value(keynum as u64, i))
+                })
+                .collect();
+            db.insert("usertable", &workload.format_key(key), &values)
+                .unwrap();
+        }
+        db
+    }
+
+    #[test]
+    fn insert_order() {
+        for insert_order in [InsertOrder::Hashed, InsertOrder::Ordered] {
+            let workload = WorkloadBuilder::default()
+                .record_count(100)
+                .operation_count(1000)
+                .proportions(StandardWorkload::A.proportions())
+                .insert_order(insert_order)
+                .seed(3)
+                .build()
+                .unwrap();
+            let mut db = loaded(&workload);
+            let report = super::run_workload_sync(&workload, &mut db);
+            assert!(report.failures.is_empty(), "{}", insert_order);
+        }
+    }
+
+    #[test]
+    fn run_workload_sync() {
+        let workload = WorkloadBuilder::default()
+            .record_count(100)
+            .operation_count(1000)
+            .proportions(StandardWorkload::F.proportions())
+            .seed(3)
+            .build()
+            .unwrap();
+        let mut db = loaded(&workload);
+        let report = super::run_workload_sync(&workload, &mut db);
+        assert!(report.failures.is_empty());
+        let count = |report: &RunReport, operation| report.histograms[&operation].count();
+        assert_eq!(
+            count(&report, Operation::Read) + count(&report, Operation::ReadModifyWrite),
+            1000
+        );
+
+        // the async runner issues the same operations for the same seed
+        let mut db = CountingDatabase::default();
+        block_on(super::run_workload(&workload, &mut db));
+        assert_eq!(db.operations["read"], 1000);
+        assert_eq!(
+            db.operations["update"],
+            count(&report, Operation::ReadModifyWrite)
+        );
+    }
+
+    #[test]
+    fn insert_into_empty_table() {
+        let workload = WorkloadBuilder::default()
+            .record_count(0)
+            .operation_count(10)
+            .proportions(OperationProportions {
+                insert: 1.0,
+                ..Default::default()
+            })
+            .build_validated()
+            .unwrap();
+        let mut db = Memory(BTreeMap::new());
+        let report = super::run_workload_sync(&workload, &mut db);
+        assert_eq!(report.operations[&Operation::Insert], 10);
+        assert_eq!(db.0.len(), 10);
+    }
+
+    #[test]
+    fn field_lengths() {
+        let workload = WorkloadBuilder::default()
+            .record_count(1)
+            .operation_count(200)
+            .proportions(OperationProportions {
+                insert: 1.0,
+                ..Default::default()
+            })
+            .field_count(2)
+            .field_specs(vec![
+                FieldSpec::new(5, Distribution::Constant),
+                FieldSpec::new(20, Distribution::Uniform),
+            ])
+            .seed(1)
+            .build()
+            .unwrap();
+        let mut db = Memory(BTreeMap::new());
+        super::run_workload_sync(&workload, &mut db);
+        assert_eq!(db.0.len(), 200);
+        let lengths = |index: usize| -> Vec<usize> {
+            db.0.values().map(|values| values[index].1.len()).collect()
+        };
+        assert!(lengths(0).iter().all(|&length| length == 5));
+        let uniform = lengths(1);
+        assert!(uniform.iter().all(|length| (1..=20).contains(length)));
+        assert!(uniform.iter().any(|&length| length < 10));
+        assert!(uniform.iter().any(|&length| length > 10));
+    }
+
+    #[test]
+    fn report_from_samples() {
+        let config = Workload::default().histogram;
+        let mut report = RunReport::new();
+        assert_eq!(report.throughput_ops_per_sec(), 0.0);
+        for ms in 1..=100 {
+            report.record(&config, Operation::Read, Duration::from_millis(ms), true);
+        }
+        for _ in 0..100 {
+            report.record(&config, Operation::Update, Duration::ZERO, false);
+        }
+        report.elapsed = Duration::from_secs(4);
+        assert_eq!(report.total_operations, 200);
+        assert_eq!(report.operations[&Operation::Read], 100);
+        assert_eq!(report.operations[&Operation::Update], 100);
+        assert_eq!(report.failures[&Operation::Update], 100);
+        assert_eq!(report.throughput_ops_per_sec(), 50.0);
+        assert_eq!(
+            report.latency_percentile(Operation::Read, 50.),
+            Some(Duration::from_millis(50))
+        );
+        assert_eq!(
+            report.latency_percentile(Operation::Read, 99.),
+            Some(Duration::from_millis(99))
+        );
+        assert_eq!(report.latency_percentile(Operation::Update, 99.), None);
+    }
+
+    #[test]
+    fn slo_violations() {
+        let config = Workload::default().histogram;
+        let mut report = RunReport::new();
+        for ms in 1..=100 {
+            report.record(&config, Operation::Read, Duration::from_millis(ms), true);
+            report.record(&config, Operation::Update, Duration::from_millis(ms), true);
+        }
+        let slo = SloConfig::default()
+            .with_p99(Operation::Read, Duration::from_millis(50))
+            .with_p99(Operation::Update, Duration::from_millis(100))
+            .with_p99(Operation::Scan, Duration::from_millis(1));
+        assert_eq!(
+            report.slo_violations(&slo),
+            ["READ p99 latency 99ms exceeds the objective of 50ms"]
+        );
+        assert!(report.slo_violations(&SloConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn validate_for() {
+        let key_value = DatabaseCapabilities {
+            supports_scan: false,
+            ..Default::default()
+        };
+        assert!(Workload::a(100, 100).validate_for(key_value).is_ok());
+        assert!(Workload::e(100, 100)
+            .validate_for(DatabaseCapabilities::default())
+            .is_ok());
+        assert!(matches!(
+            Workload::e(100, 100).validate_for(key_value),
+            Err(WorkloadError::UnsupportedOperation {
+                operation: Operation::Scan,
+                proportion
+            }) if proportion == 0.95
+        ));
+    }
+
+    #[test]
+    fn write_operation_plan_csv() {
+        let workload = WorkloadBuilder::default()
+            .record_count(100)
+            .operation_count(100)
+            .proportions(StandardWorkload::E.proportions())
+            .max_scan_length(10)
+            .seed(5)
+            .build()
+            .unwrap();
+        let mut csv = vec![];
+        workload.write_operation_plan_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let mut again = vec![];
+        workload.write_operation_plan_csv(&mut again).unwrap();
+        assert_eq!(csv.as_bytes(), again);
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("sequence,operation,key,scanlength"));
+        for (i, line) in lines.enumerate() {
+            let columns: Vec<_> = line.split(',').collect();
+            assert_eq!(columns[0], i.to_string());
+            assert!(columns[2].starts_with("user"));
+            match columns[1] {
+                "SCAN" => assert!((1..=10).contains(&columns[3].parse::<u64>().unwrap())),
+                "INSERT" => assert_eq!(columns[3], ""),
+                operation => panic!("unexpected {}", operation),
+            }
+        }
+
+        struct Closed;
+        impl Write for Closed {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::BrokenPipe.into())
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+        assert!(matches!(
+            workload.write_operation_plan_csv(Closed),
+            Err(WorkloadError::Write(_))
+        ));
+        let invalid = WorkloadBuilder::default()
+            .record_count(100)
+            .operation_count(100)
+            .read_proportion(0.5)
+            .build()
+            .unwrap();
+        let mut csv = vec![];
+        assert!(matches!(
+            invalid.write_operation_plan_csv(&mut csv),
+            Err(WorkloadError::ProportionsOutOfRange { .. })
+        ));
+        assert!(csv.is_empty());
+        let empty = WorkloadBuilder::default().record_count(0).build().unwrap();
+        assert!(matches!(
+            empty.write_operation_plan_csv(&mut csv),
+            Err(WorkloadError::NoRecords { .. })
+        ));
+        assert!(csv.is_empty());
+    }
+}
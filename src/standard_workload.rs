@@ -0,0 +1,155 @@
+use std::{fmt, str::FromStr};
+
+use crate::{OperationProportions, ParseVariantError, Workload};
+
+/// The core workloads shipped with YCSB
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum StandardWorkload {
+    /// Update heavy: 50% reads, 50% updates
+    A,
+    /// Read mostly: 95% reads, 5% updates
+    B,
+    /// Read only
+    C,
+    /// Read latest: 95% reads of recently inserted records, 5% inserts
+    D,
+    /// Short ranges: 95% scans, 5% inserts
+    E,
+    /// Read-modify-write: 50% reads, 50% read-modify-writes
+    F,
+}
+
+impl StandardWorkload {
+    pub const ALL: [StandardWorkload; 6] = [Self::A, Self::B, Self::C, Self::D, Self::E, Self::F];
+
+    pub const fn proportions(&self) -> OperationProportions {
+        let (read, update, insert, scan, read_modify_write) = match self {
+            Self::A => (0.5, 0.5, 0., 0., 0.),
+            Self::B => (0.95, 0.05, 0., 0., 0.),
+            Self::C => (1., 0., 0., 0., 0.),
+            Self::D => (0.95, 0., 0.05, 0., 0.),
+            Self::E => (0., 0., 0.05, 0.95, 0.),
+            Self::F => (0.5, 0., 0., 0., 0.5),
+        };
+        OperationProportions {
+            read,
+            update,
+            insert,
+            scan,
+            read_modify_write,
+            delete: 0.,
+        }
+    }
+
+    /// The name of the workload file shipped with YCSB
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::A => "workloada",
+            Self::B => "workloadb",
+            Self::C => "workloadc",
+            Self::D => "workloadd",
+            Self::E => "workloade",
+            Self::F => "workloadf",
+        }
+    }
+
+    /// The workload file shipped with YCSB, embedded at compile time
+    pub fn toml(&self) -> &'static str {
+        match self {
+            Self::A => include_str!("../workloads/workloada.toml"),
+            Self::B => include_str!("../workloads/workloadb.toml"),
+            Self::C => include_str!("../workloads/workloadc.toml"),
+            Self::D => include_str!("../workloads/workloadd.toml"),
+            Self::E => include_str!("../workloads/workloade.toml"),
+            Self::F => include_str!("../workloads/workloadf.toml"),
+        }
+    }
+
+    pub fn build(self, record_count: u64, operation_count: u64) -> Workload {
+        match self {
+            Self::A => Workload::a(record_count, operation_count),
+            Self::B => Workload::b(record_count, operation_count),
+            Self::C => Workload::c(record_count, operation_count),
+            Self::D => Workload::d(record_count, operation_count),
+            Self::E => Workload::e(record_count, operation_count),
+            Self::F => Workload::f(record_count, operation_count),
+        }
+    }
+}
+
+impl fmt::Display for StandardWorkload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for StandardWorkload {
+    type Err = ParseVariantError;
+
+    /// Accepts either the letter or the file name of the workload,
+    /// ignoring case, e.g. `a`, `A` or `workloada`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.to_ascii_lowercase();
+        match lower.strip_prefix("workload").unwrap_or(&lower) {
+            "a" => Ok(Self::A),
+            "b" => Ok(Self::B),
+            "c" => Ok(Self::C),
+            "d" => Ok(Self::D),
+            "e" => Ok(Self::E),
+            "f" => Ok(Self::F),
+            _ => Err(ParseVariantError {
+                kind: "standard workload",
+                value: s.to_owned(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proportions_sum_to_one() {
+        for standard in StandardWorkload::ALL {
+            let p = standard.proportions();
+            let sum = p.read + p.update + p.insert + p.scan + p.read_modify_write + p.delete;
+            assert!((sum - 1.0).abs() < 1e-9, "{:?}", standard);
+        }
+    }
+
+    #[test]
+    fn from_str() {
+        for s in ["a", "A", "workloada", "WorkloadA"] {
+            assert_eq!(s.parse(), Ok(StandardWorkload::A));
+        }
+        for standard in StandardWorkload::ALL {
+            assert_eq!(standard.to_string().parse(), Ok(standard));
+        }
+        assert!("g".parse::<StandardWorkload>().is_err());
+        assert!("workload".parse::<StandardWorkload>().is_err());
+    }
+
+    #[test]
+    fn build() {
+        assert_eq!(StandardWorkload::E.build(10, 20), Workload::e(10, 20));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn embedded() {
+        for standard in StandardWorkload::ALL {
+            let workload = Workload::standard(standard);
+            let p = standard.proportions();
+            assert_eq!(workload.read_proportion, p.read, "{}", standard);
+            assert_eq!(workload.update_proportion, p.update, "{}", standard);
+            assert_eq!(workload.insert_proportion, p.insert, "{}", standard);
+            assert_eq!(workload.scan_proportion, p.scan, "{}", standard);
+            assert_eq!(
+                workload.read_modify_write_proportion, p.read_modify_write,
+                "{}",
+                standard
+            );
+        }
+    }
+}
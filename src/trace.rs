@@ -0,0 +1,156 @@
+//! Approximating a captured operation trace with a workload
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+use crate::{Distribution, Operation, OperationProportions, Workload, WorkloadError};
+
+/// Key accesses whose variance exceeds their mean by this factor are
+/// considered skewed. Uniformly chosen keys are accessed a Poisson
+/// distributed number of times, whose variance equals its mean.
+const SKEW_DISPERSION: f64 = 2.0;
+
+impl Workload {
+    /// A workload with the operation proportions, record count and
+    /// operation count observed in the trace at `path`, see
+    /// [`Workload::from_trace_summary_reader`]
+    pub fn from_trace_summary<P: AsRef<Path>>(path: P) -> Result<Self, WorkloadError> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|source| WorkloadError::ReadFile {
+            path: path.to_owned(),
+            source,
+        })?;
+        Workload::from_trace_summary_reader(BufReader::new(file))
+    }
+
+    /// A workload approximating a trace with one `OPERATION key` line per
+    /// operation, e.g. `READ user42`, where the operation is labelled as
+    /// in YCSB's output. Blank lines are skipped.
+    ///
+    /// The record count is the number of distinct keys operated on by
+    /// anything but inserts. The request distribution is zipfian if some
+    /// keys are accessed much more often than others, and uniform otherwise.
+    pub fn from_trace_summary_reader<R: BufRead>(reader: R) -> Result<Self, WorkloadError> {
+        let mut operations = BTreeMap::<Operation, u64>::new();
+        let mut accesses = HashMap::<String, u64>::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let malformed = || WorkloadError::MalformedTrace {
+                line: index + 1,
+                content: line.to_owned(),
+            };
+            let (operation, key) = line.split_once(char::is_whitespace).ok_or_else(malformed)?;
+            let operation: Operation = operation.parse().map_err(|_| malformed())?;
+            *operations.entry(operation).or_default() += 1;
+            if operation != Operation::Insert {
+                *accesses.entry(key.trim().to_owned()).or_default() += 1;
+            }
+        }
+        let operation_count: u64 = operations.values().sum();
+        if operation_count == 0 {
+            return Err(WorkloadError::EmptyTrace);
+        }
+        let proportion = |operation| {
+            operations.get(&operation).copied().unwrap_or_default() as f64 / operation_count as f64
+        };
+        let proportions = OperationProportions {
+            read: proportion(Operation::Read),
+            update: proportion(Operation::Update),
+            insert: proportion(Operation::Insert),
+            scan: proportion(Operation::Scan),
+            read_modify_write: proportion(Operation::ReadModifyWrite),
+            delete: proportion(Operation::Delete),
+        };
+        let request_distribution = if is_skewed(accesses.values().copied()) {
+            Distribution::Zipfian
+        } else {
+            Distribution::Uniform
+        };
+        Ok(Workload::builder()
+            .record_count(accesses.len().max(1) as u64)
+            .operation_count(operation_count)
+            .proportions(proportions)
+            .request_distribution(request_distribution)
+            .build()?)
+    }
+}
+
+/// Whether the number of accesses to each key varies much more than if
+/// keys were chosen uniformly
+fn is_skewed(accesses: impl Iterator<Item = u64>) -> bool {
+    let accesses: Vec<_> = accesses.map(|n| n as f64).collect();
+    if accesses.len() < 2 {
+        return false;
+    }
+    let mean = accesses.iter().sum::<f64>() / accesses.len() as f64;
+    let variance = accesses.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / accesses.len() as f64;
+    variance / mean > SKEW_DISPERSION
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    use super::*;
+    use crate::generator::{Generator, UniformLongGenerator, ZipfianGenerator};
+
+    fn trace(keys: impl Generator<u64>) -> String {
+        let mut rng = SmallRng::seed_from_u64(0);
+        (0..10000)
+            .map(|i| {
+                let operation = if i % 10 == 0 { "UPDATE" } else { "READ" };
+                format!("{} user{}\n", operation, keys.next_value(&mut rng))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn uniform_trace() {
+        let trace = trace(UniformLongGenerator::new(0, 99));
+        let config = Workload::from_trace_summary_reader(trace.as_bytes()).unwrap();
+        assert_eq!(config.operation_count(), 10000);
+        assert_eq!(config.record_count(), 100);
+        assert_eq!(config.read_proportion(), 0.9);
+        assert_eq!(config.update_proportion(), 0.1);
+        assert_eq!(config.request_distribution(), Distribution::Uniform);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn skewed_trace() {
+        let trace = trace(ZipfianGenerator::from_items(100));
+        let path = std::env::temp_dir().join("yc3b_skewed_trace.txt");
+        fs::write(&path, trace).unwrap();
+        let config = Workload::from_trace_summary(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(config.request_distribution(), Distribution::Zipfian);
+    }
+
+    #[test]
+    fn malformed_trace() {
+        let trace = "READ user1\n\nINSERT user2\nGET user3\n";
+        assert!(matches!(
+            Workload::from_trace_summary_reader(trace.as_bytes()),
+            Err(WorkloadError::MalformedTrace { line: 4, content }) if content == "GET user3"
+        ));
+        assert!(Workload::from_trace_summary_reader("READ\n".as_bytes()).is_err());
+        assert!(matches!(
+            Workload::from_trace_summary_reader("\n".as_bytes()),
+            Err(WorkloadError::EmptyTrace)
+        ));
+        assert!(matches!(
+            Workload::from_trace_summary("/nonexistent/trace"),
+            Err(WorkloadError::ReadFile { .. })
+        ));
+    }
+}
@@ -12,6 +12,7 @@ use crate::generator::{
     Generator, UniformLongGenerator, WeightPair, ZipfianGenerator,
 };
 use crate::properties::Properties;
+use yc3b::fnv_hash64;
 
 #[derive(Copy, Clone, Debug)]
 pub enum CoreOperation {
@@ -85,7 +86,7 @@ impl CoreWorkload {
 
     fn do_transaction_read(&self, db: Rc<dyn DB>) {
         let keynum = self.next_key_num();
-        let dbkey = format!("{}", fnvhash64(keynum));
+        let dbkey = format!("{}", fnv_hash64(keynum));
         let mut result = HashMap::new();
         db.read(&self.table, &dbkey, &mut result).unwrap();
         // TODO: verify rows
@@ -108,7 +109,7 @@ impl Workload for CoreWorkload {
             .lock()
             .unwrap()
             .next_value(&mut self.rng.lock().unwrap());
-        let dbkey = format!("{}", fnvhash64(dbkey));
+        let dbkey = format!("{}", fnv_hash64(dbkey));
         let mut values = HashMap::new();
         for field_name in &self.field_names {
             let field_len = self
@@ -138,20 +139,6 @@ impl Workload for CoreWorkload {
     }
 }
 
-// http://en.wikipedia.org/wiki/Fowler_Noll_Vo_hash
-fn fnvhash64(val: u64) -> u64 {
-    let mut val = val;
-    let prime = 0xcbf29ce484222325;
-    let mut hashval = prime;
-    for _ in 0..8 {
-        let octet = val & 0x00ff;
-        val >>= 8;
-        hashval ^= octet;
-        hashval = hashval.wrapping_mul(prime);
-    }
-    hashval
-}
-
 fn get_field_length_generator(prop: &Properties) -> Box<dyn Generator<u64> + Send> {
     match prop.field_length_distribution.to_lowercase().as_str() {
         "constant" => Box::new(ConstantGenerator::new(prop.field_length)),